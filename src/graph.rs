@@ -1,9 +1,28 @@
 // Graph with generic type
-// Nodes are encoded as HashMap<Node<T>,U> U being a value for each node
-// Edges are encoded as HashSet<Tuple<Node,Node>>
+// Vertices are encoded as HashSet<T> and edges as HashSet<(T,T)>
+// Labels, values of type U and edge weights are stored in separate HashMaps keyed by vertex or edge
+// Inbound and outbound adjacency tables map every vertex to a Vec of its neighbours
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::BinaryHeap;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::fmt::{Debug, Display};
 use std::hash::Hash;
+use std::sync::OnceLock;
+
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::seq::SliceRandom;
+use rand::Rng;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+#[cfg(feature = "serde")]
+use serde::de::Error;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::union_find::UnionFind;
 
 #[derive(Debug)]
 #[derive(PartialEq)]
@@ -15,14 +34,32 @@ pub enum GraphError {
     /// There is no such edge in the graph
     NoSuchEdge,
 
+    /// The graph contains a cycle although the operation requires it to be acyclic
+    CycleDetected,
+
+}
+
+
+#[derive(Clone, Copy)]
+/// Visiting state of a vertex during a depth first search
+enum Colour {
+    /// The vertex is on the stack of the search
+    Grey,
+
+    /// The search has finished the vertex
+    Black,
 }
 
 
+#[derive(Clone)]
 /// Graph structure where keys are usually primitive like tuples of lists in order to store 
 /// gamestates of a game as a graph with e.g. their respective ratings.
 /// Vertices and edges are implemented as sets of T and (T,T) tuples respectively and labels are
 /// implemented as a hashmap,  just like the adjacency tables of the vertices.
-pub struct Graph<T: Eq + PartialEq + Hash + Copy> {
+/// Every vertex can additionally store a value of type U, e.g. the rating of a gamestate.
+/// Edge weights are always u64, so algorithms like dijkstra, max_flow and min_cut can rely on
+/// integer arithmetic. Edges without a weight count as weight 1 in those algorithms.
+pub struct Graph<T: Eq + PartialEq + Hash + Copy, U = ()> {
     /// Set of vertices in the graph
     vertices: HashSet<T>,
 
@@ -32,29 +69,46 @@ pub struct Graph<T: Eq + PartialEq + Hash + Copy> {
     /// Mapping of vertices to their labels
     vertex_labels: HashMap<T, String>,
 
+    /// Mapping of vertices to their values
+    vertex_values: HashMap<T, U>,
+
+    /// Mapping of edges to their weights. Edges without a weight have no entry
+    edge_weights: HashMap<(T, T), u64>,
+
     /// Mapping of vertices to vector of their inbound neighbours
     inbound_table: HashMap<T, Vec<T>>,
 
     /// Mapping of vertices to vector of their inbound neighbours
     outbound_table: HashMap<T, Vec<T>>,
+
+    /// Union-find structure over the weakly connected components. Empty if it was invalidated by
+    /// a removal and has to be rebuilt. Kept in a OnceLock so component_count can rebuild it
+    /// through a shared reference while the graph stays Sync
+    components: OnceLock<UnionFind<T>>,
 }
 
 
-impl<T: Eq + PartialEq + Hash + Copy> Graph<T> {
+impl<T: Eq + PartialEq + Hash + Copy, U> Graph<T, U> {
     /// Creates a new graph
-    pub fn new() -> Graph<T> {
+    pub fn new() -> Graph<T, U> {
         Graph {
             vertices: HashSet::new(),
             edges: HashSet::new(),
             vertex_labels: HashMap::new(),
+            vertex_values: HashMap::new(),
+            edge_weights: HashMap::new(),
             inbound_table: HashMap::new(),
             outbound_table: HashMap::new(),
+            components: OnceLock::from(UnionFind::new()),
         }
     }
     
     /// Adds a vertex. If vertex with given key was in graph already returns true. Otherwise
     /// false
     pub fn add_vertex(&mut self, vertex: T) -> bool {
+        if let Some(components) = self.components.get_mut() {
+            components.insert(vertex);
+        }
         self.vertices.insert(vertex)
     }
 
@@ -62,7 +116,7 @@ impl<T: Eq + PartialEq + Hash + Copy> Graph<T> {
     /// key was in graph already returns true. Otherwise false. The label is the newly given in any case
     pub fn add_vertex_with_label(&mut self, vertex: T, label: &str) -> bool {
         self.vertex_labels.insert(vertex, label.to_owned());
-        self.vertices.insert(vertex)
+        self.add_vertex(vertex)
     }
 
     /// Adds an edge from outbound to incoming to do add error when edge already exists
@@ -71,15 +125,19 @@ impl<T: Eq + PartialEq + Hash + Copy> Graph<T> {
             return Err(GraphError::NoSuchVertex);
         }
 
-        self.edges.insert((outbound, incoming));
+        // The adjacency tables already contain the edge if it was in the graph before
+        if !self.edges.insert((outbound, incoming)) {
+            return Ok(());
+        }
+        if let Some(components) = self.components.get_mut() {
+            components.union(outbound, incoming);
+        }
 
         // Add outbound edge to adjacency table of incoming vertex
         match self.inbound_table.get_mut(&incoming) {
             Some(inbounds) => {inbounds.push(outbound)},
             None => {
-                let mut v: Vec<T> = Vec::new();
-                v.push(outbound);
-                self.inbound_table.insert(incoming, v);
+                self.inbound_table.insert(incoming, vec![outbound]);
             }
         }
 
@@ -87,14 +145,51 @@ impl<T: Eq + PartialEq + Hash + Copy> Graph<T> {
         match self.outbound_table.get_mut(&outbound) {
             Some(outbounds) => {outbounds.push(incoming);},
             None => {
-                let mut v: Vec<T> = Vec::new();
-                v.push(incoming);
-                self.outbound_table.insert(outbound, v);
+                self.outbound_table.insert(outbound, vec![incoming]);
             }
         }
         Ok(())
     }
 
+    /// Adds an edge from outbound to incoming with the given weight. If the edge exists already
+    /// its weight is updated
+    pub fn add_weighted_edge(&mut self, outbound: T, incoming: T, weight: u64) -> Result<(), GraphError> {
+        self.add_edge(outbound, incoming)?;
+        self.edge_weights.insert((outbound, incoming), weight);
+        Ok(())
+    }
+
+    /// Adds all vertices of the path and an edge between every pair of consecutive vertices
+    pub fn add_path(&mut self, path: &[T]) -> Result<(), GraphError> {
+        for vertex in path {
+            self.add_vertex(*vertex);
+        }
+        for pair in path.windows(2) {
+            self.add_edge(pair[0], pair[1])?;
+        }
+        Ok(())
+    }
+
+    /// Adds all vertices, an edge between every pair of consecutive vertices and an edge from the
+    /// last vertex back to the first. A single vertex gets a self-loop
+    pub fn add_cycle(&mut self, vertices: &[T]) -> Result<(), GraphError> {
+        self.add_path(vertices)?;
+        if let (Some(first), Some(last)) = (vertices.first(), vertices.last()) {
+            self.add_edge(*last, *first)?;
+        }
+        Ok(())
+    }
+
+    /// Adds the edges (a, b) and (b, a). A self-loop is only added once.
+    /// Returns NoSuchVertex GraphError, if a or b is not in graph
+    pub fn add_undirected_edge(&mut self, a: T, b: T) -> Result<(), GraphError> {
+        if !self.vertices.contains(&a) || !self.vertices.contains(&b) {
+            return Err(GraphError::NoSuchVertex);
+        }
+        self.add_edge(a, b)?;
+        self.add_edge(b, a)
+    }
+
     /// Returns the number of vertices
     pub fn number_of_vertices(&self) -> usize {
         self.vertices.len()
@@ -105,15 +200,28 @@ impl<T: Eq + PartialEq + Hash + Copy> Graph<T> {
         self.edges.len()
     }
 
+    /// Returns an iterator over all vertices of the graph. The iteration order is unspecified
+    pub fn vertices(&self) -> impl Iterator<Item = &T> + '_ {
+        self.vertices.iter()
+    }
+
+    /// Returns an iterator over all edges of the graph as (outbound, inbound) tuples. Each edge is
+    /// yielded once and the iteration order is unspecified
+    pub fn edges(&self) -> impl Iterator<Item = &(T, T)> + '_ {
+        self.edges.iter()
+    }
+
     /// Removes a vertex to do: remove label from labels
     pub fn remove_vertex(&mut self, vertex: &T) -> Result<(), GraphError> {
         if !self.vertices.contains(vertex) {
             return Err(GraphError::NoSuchVertex);
         }
 
-        // Remove vertex from vertices and remove label if present
+        // Remove vertex from vertices and remove label and value if present
         self.vertices.remove(vertex);
+        self.components = OnceLock::new();
         self.vertex_labels.remove(vertex);
+        self.vertex_values.remove(vertex);
 
         // Remove outgoing edges with other vertices
         if let Some(outbound) = self.outbound_table.remove(vertex) {
@@ -131,15 +239,83 @@ impl<T: Eq + PartialEq + Hash + Copy> Graph<T> {
         Ok(())
     }
 
-    /// Removes an edge
+    /// Removes an edge together with its weight and its entries in the adjacency tables.
+    /// Returns NoSuchEdge GraphError, if the edge is not in graph
     pub fn remove_edge(&mut self, inbound: &T, outbound: &T) -> Result<(), GraphError> {
-        if !self.edges.remove(&(*inbound,*outbound)) {
+        if self.detach_edge(inbound, outbound) {
             Ok(())
         } else {
             Err(GraphError::NoSuchEdge)
         }
     }
 
+    /// Removes the edge (a, b) together with its weight and every entry of b in the outbound table
+    /// of a and of a in the inbound table of b, including duplicate entries left by older versions
+    /// of add_edge. Returns NoSuchVertex GraphError, if a or b is not in graph
+    pub fn remove_all_edges_between(&mut self, a: &T, b: &T) -> Result<(), GraphError> {
+        if !self.vertices.contains(a) || !self.vertices.contains(b) {
+            return Err(GraphError::NoSuchVertex);
+        }
+        self.edges.remove(&(*a, *b));
+        self.edge_weights.remove(&(*a, *b));
+        self.purge_adjacency(a, b);
+        Ok(())
+    }
+
+    /// Removes the edges (a, b) and (b, a) together with their weights.
+    /// Returns NoSuchVertex GraphError, if a or b is not in graph and NoSuchEdge GraphError, if
+    /// one of the two edges is not in graph. In both cases no edge is removed
+    pub fn remove_undirected_edge(&mut self, a: &T, b: &T) -> Result<(), GraphError> {
+        if !self.vertices.contains(a) || !self.vertices.contains(b) {
+            return Err(GraphError::NoSuchVertex);
+        }
+        if !self.edges.contains(&(*a, *b)) || !self.edges.contains(&(*b, *a)) {
+            return Err(GraphError::NoSuchEdge);
+        }
+        self.detach_edge(a, b);
+        self.detach_edge(b, a);
+        Ok(())
+    }
+
+    /// Removes all inbound and outbound edges of a vertex but keeps the vertex itself. Returns the
+    /// number of removed edges and NoSuchVertex GraphError, if vertex is not in graph
+    pub fn remove_incident_edges(&mut self, vertex: &T) -> Result<usize, GraphError> {
+        if !self.vertices.contains(vertex) {
+            return Err(GraphError::NoSuchVertex);
+        }
+
+        let outbound: Vec<T> = self.out_neighbours(vertex).copied().collect();
+        let inbound: Vec<T> = self.in_neighbours(vertex).copied().collect();
+        let mut removed = 0;
+        for other_vertex in outbound {
+            if self.detach_edge(vertex, &other_vertex) {
+                removed += 1;
+            }
+        }
+        for other_vertex in inbound {
+            if self.detach_edge(&other_vertex, vertex) {
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+
+    /// Removes all vertices, edges, labels, values and weights while keeping the allocated memory
+    /// of the graph for reuse
+    pub fn clear(&mut self) {
+        self.vertices.clear();
+        self.edges.clear();
+        self.vertex_labels.clear();
+        self.vertex_values.clear();
+        self.edge_weights.clear();
+        self.inbound_table.clear();
+        self.outbound_table.clear();
+        match self.components.get_mut() {
+            Some(components) => components.clear(),
+            None => self.components = OnceLock::from(UnionFind::new()),
+        }
+    }
+
     /// Returns the label of a vertex as readable reference
     pub fn get_label(&self, vertex: &T) -> Option<&String> {
         self.vertex_labels.get(vertex)
@@ -150,17 +326,67 @@ impl<T: Eq + PartialEq + Hash + Copy> Graph<T> {
         self.vertex_labels.get_mut(vertex)
     }
 
+    /// Returns the weight of an edge as readable reference or None if the edge has no weight
+    pub fn edge_weight(&self, outbound: &T, incoming: &T) -> Option<&u64> {
+        self.edge_weights.get(&(*outbound, *incoming))
+    }
+
+    /// Returns the weight of an edge as mutable reference or None if the edge has no weight
+    pub fn edge_weight_mut(&mut self, outbound: &T, incoming: &T) -> Option<&mut u64> {
+        self.edge_weights.get_mut(&(*outbound, *incoming))
+    }
+
     /// Sets the label of a vertex or updates it if none was present
     /// Returns NoSuchVertex GraphError, if vertex is not in graph
     pub fn set_label(&mut self, vertex: &T, label: &str) -> Result<(), GraphError> {
         if !self.vertices.contains(vertex) {
-            return Err(GraphError::NoSuchVertex)
+            Err(GraphError::NoSuchVertex)
         } else {
             self.vertex_labels.insert(*vertex, label.to_owned());
             Ok(())
         }
     }
 
+    /// Exchanges the labels of two vertices. If only one of them has a label, the label is moved
+    /// to the other vertex and the first is left without a label.
+    /// Returns NoSuchVertex GraphError, if a or b is not in graph
+    pub fn swap_labels(&mut self, a: &T, b: &T) -> Result<(), GraphError> {
+        if !self.vertices.contains(a) || !self.vertices.contains(b) {
+            return Err(GraphError::NoSuchVertex);
+        }
+
+        let label_a = self.vertex_labels.remove(a);
+        let label_b = self.vertex_labels.remove(b);
+        if let Some(label) = label_a {
+            self.vertex_labels.insert(*b, label);
+        }
+        if let Some(label) = label_b {
+            self.vertex_labels.insert(*a, label);
+        }
+        Ok(())
+    }
+
+    /// Returns the value of a vertex as readable reference
+    pub fn get_value(&self, vertex: &T) -> Option<&U> {
+        self.vertex_values.get(vertex)
+    }
+
+    /// Returns the value of a vertex as mutable reference
+    pub fn get_value_mut(&mut self, vertex: &T) -> Option<&mut U> {
+        self.vertex_values.get_mut(vertex)
+    }
+
+    /// Sets the value of a vertex or overwrites it if one was present
+    /// Returns NoSuchVertex GraphError, if vertex is not in graph
+    pub fn set_value(&mut self, vertex: &T, value: U) -> Result<(), GraphError> {
+        if !self.vertices.contains(vertex) {
+            Err(GraphError::NoSuchVertex)
+        } else {
+            self.vertex_values.insert(*vertex, value);
+            Ok(())
+        }
+    }
+
     /// Returns an iterator with the ingoing neighbours of the given vertex
     pub fn in_neighbours(&self, vertex: &T) -> std::slice::Iter<'_, T>{
         match self.inbound_table.get(vertex) {
@@ -177,6 +403,22 @@ impl<T: Eq + PartialEq + Hash + Copy> Graph<T> {
         }
     }
 
+    /// Returns the number of outbound edges of the given vertex or None if vertex is not in graph
+    pub fn out_degree(&self, vertex: &T) -> Option<usize> {
+        if !self.vertices.contains(vertex) {
+            return None;
+        }
+        Some(self.out_neighbours(vertex).len())
+    }
+
+    /// Returns the number of inbound edges of the given vertex or None if vertex is not in graph
+    pub fn in_degree(&self, vertex: &T) -> Option<usize> {
+        if !self.vertices.contains(vertex) {
+            return None;
+        }
+        Some(self.in_neighbours(vertex).len())
+    }
+
     /// Returns whether a vertex is in the graph by key
     pub fn is_vertex_in_graph(&self, vertex: &T) -> bool {
         self.vertices.contains(vertex)
@@ -186,176 +428,4304 @@ impl<T: Eq + PartialEq + Hash + Copy> Graph<T> {
     pub fn is_edge_in_graph(&self, outbound: T, inbound: T) -> bool {
         self.edges.contains(&(outbound, inbound))
     }
-}
 
+    /// Returns the induced subgraph on the given vertices. Vertices which are not in the graph are
+    /// ignored and edges are kept if both of their endpoints are kept. Labels, values and weights
+    /// are carried over
+    pub fn subgraph(&self, vertices: &HashSet<T>) -> Graph<T, U> where U: Clone {
+        let mut subgraph = Graph::new();
+        for vertex in self.vertices.intersection(vertices) {
+            match self.vertex_labels.get(vertex) {
+                Some(label) => {subgraph.add_vertex_with_label(*vertex, label);},
+                None => {subgraph.add_vertex(*vertex);},
+            }
+            if let Some(value) = self.vertex_values.get(vertex) {
+                subgraph.vertex_values.insert(*vertex, value.clone());
+            }
+        }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        for (outbound, incoming) in &self.edges {
+            if subgraph.is_vertex_in_graph(outbound) && subgraph.is_vertex_in_graph(incoming) {
+                let _ = subgraph.add_edge(*outbound, *incoming);
+                if let Some(weight) = self.edge_weights.get(&(*outbound, *incoming)) {
+                    subgraph.edge_weights.insert((*outbound, *incoming), *weight);
+                }
+            }
+        }
+        subgraph
+    }
 
-    #[test]
-    fn creating_empty_graph() {
-        let g: Graph<u32> = Graph::new();
-        assert_eq!(g.edges, HashSet::new());
-        assert_eq!(g.vertex_labels, HashMap::new());
-        assert_eq!(g.inbound_table, HashMap::new());
-        assert_eq!(g.outbound_table, HashMap::new());
+    /// Returns a random induced subgraph where each vertex is kept with probability
+    /// vertex_fraction, which is clamped to [0, 1]. A NaN fraction keeps no vertex. See subgraph
+    /// for what is carried over
+    pub fn random_subgraph(&self, vertex_fraction: f64, rng: &mut impl Rng) -> Graph<T, U> where U: Clone {
+        let probability = if vertex_fraction.is_nan() { 0.0 } else { vertex_fraction.clamp(0.0, 1.0) };
+        let kept: HashSet<T> = self.vertices.iter()
+            .filter(|_| rng.gen_bool(probability))
+            .copied()
+            .collect();
+        self.subgraph(&kept)
     }
 
-    #[test]
-    fn adding_vertex_to_graph() {
-        let mut g: Graph<u32> = Graph::new();
-        g.add_vertex(32);
-        assert!(g.vertices.contains(&32));
+    /// Returns the core number of each vertex, that is the largest k such that the vertex
+    /// belongs to the k-core of the undirected projection of the graph. Self-loops are ignored
+    pub fn core_numbers(&self) -> HashMap<T, usize> {
+        let mut degrees: HashMap<T, usize> = self.vertices.iter()
+            .map(|vertex| (*vertex, self.undirected_neighbours(vertex).len()))
+            .collect();
+        let max_degree = degrees.values().copied().max().unwrap_or(0);
+
+        // Bucket vertices by their current degree. Buckets may contain stale entries which are
+        // skipped when their degree no longer matches the bucket
+        let mut buckets: Vec<Vec<T>> = vec![Vec::new(); max_degree + 1];
+        for (vertex, degree) in &degrees {
+            buckets[*degree].push(*vertex);
+        }
+
+        let mut core_numbers: HashMap<T, usize> = HashMap::new();
+        let mut k = 0;
+        while core_numbers.len() < self.vertices.len() {
+            let mut current = 0;
+            while buckets[current].is_empty() {
+                current += 1;
+            }
+            let vertex = buckets[current].pop().unwrap();
+            if core_numbers.contains_key(&vertex) || degrees[&vertex] != current {
+                continue;
+            }
+
+            // Peel the vertex and decrease the degree of its remaining neighbours
+            k = k.max(current);
+            core_numbers.insert(vertex, k);
+            for neighbour in self.undirected_neighbours(&vertex) {
+                if core_numbers.contains_key(&neighbour) {
+                    continue;
+                }
+                if let Some(degree) = degrees.get_mut(&neighbour) {
+                    *degree -= 1;
+                    buckets[*degree].push(neighbour);
+                }
+            }
+        }
+        core_numbers
     }
 
-    #[test]
-    fn adding_multiple_vertices() {
-        let mut g: Graph<u32> = Graph::new();
-        g.add_vertex(32);
-        g.add_vertex(1);
-        g.add_vertex(2);
-        assert!(g.vertices.contains(&32));
-        assert!(g.vertices.contains(&1));
-        assert!(g.vertices.contains(&2));
-        assert!(!g.vertices.contains(&31));
-        assert_eq!(g.vertices.len(),3);
-        assert_eq!(g.number_of_vertices(),3);
+    /// Returns the number of distinct labels assigned to vertices
+    pub fn distinct_label_count(&self) -> usize {
+        self.vertex_labels.values().collect::<HashSet<&String>>().len()
     }
 
-    #[test]
-    fn adding_edges() {
-        let mut g: Graph<u32> = Graph::new();
-        assert_eq!(g.number_of_edges(), 0);
-        g.add_vertex(2);
-        g.add_vertex(3);
-        let i = 3;
-        g.add_edge(2, i).unwrap();
-        g.add_edge(i, i).unwrap();
-    } 
+    /// Removes all edges whose weight lies outside of [lo, hi] as well as all edges without a
+    /// weight
+    pub fn retain_edges_in_weight_band(&mut self, lo: u64, hi: u64) {
+        let to_remove: Vec<(T, T)> = self.edges.iter()
+            .filter(|edge| match self.edge_weights.get(edge) {
+                Some(weight) => *weight < lo || *weight > hi,
+                None => true,
+            })
+            .copied()
+            .collect();
 
-    #[test]
-    fn removing_vertices_and_simultaneously_removing_edges() {
-        let mut g: Graph<u32> = Graph::new();
-        assert_eq!(g.number_of_edges(), 0);
-        g.add_vertex(2);
-        g.add_vertex(3);
-        let i = 3;
-        g.add_edge(i, i).unwrap();
-        g.add_edge(2, i).unwrap();
-        g.remove_vertex(&i).unwrap();
-        assert!(!g.edges.contains(&(i,i)));
+        for (outbound, incoming) in to_remove {
+            self.detach_edge(&outbound, &incoming);
+        }
     }
 
-    #[test]
-    fn add_vertices_with_label_and_get_label() {
-        let mut g: Graph<u32> = Graph::new();
-        assert_eq!(g.number_of_vertices(), 0);
-        g.add_vertex_with_label(1, "A");
-        g.add_vertex_with_label(2, "B");
-        g.add_vertex(3);
-        assert_eq!(g.number_of_vertices(), 3);
+    /// Returns the degree assortativity, that is the Pearson correlation of the degrees at both
+    /// ends of the edges in the undirected projection of the graph. Returns 0.0 for graphs where
+    /// the correlation is not defined, e.g. without edges or where all degrees are equal
+    pub fn degree_assortativity(&self) -> f64 {
+        let degrees: HashMap<T, f64> = self.vertices.iter()
+            .map(|vertex| (*vertex, self.undirected_neighbours(vertex).len() as f64))
+            .collect();
 
-        g.add_vertex_with_label(4,"");
-        assert_eq!(g.get_label(&1).unwrap(), "A");
+        // Every undirected edge is visited in both directions which makes the correlation symmetric
+        let mut pairs: Vec<(f64, f64)> = Vec::new();
+        for vertex in &self.vertices {
+            for neighbour in self.undirected_neighbours(vertex) {
+                pairs.push((degrees[vertex], degrees[&neighbour]));
+            }
+        }
+        if pairs.is_empty() {
+            return 0.0;
+        }
 
-        if let Err(e) = g.remove_vertex(&2) {panic!("Error: {:?}", e);}
+        let n = pairs.len() as f64;
+        let mean_x = pairs.iter().map(|(x, _)| x).sum::<f64>() / n;
+        let mean_y = pairs.iter().map(|(_, y)| y).sum::<f64>() / n;
+        let covariance: f64 = pairs.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+        let variance_x: f64 = pairs.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+        let variance_y: f64 = pairs.iter().map(|(_, y)| (y - mean_y).powi(2)).sum();
 
-        assert_eq!(g.get_label(&2), Option::None);
-        assert_eq!(g.get_label(&3), Option::None);
-        assert_eq!(g.get_label(&4).unwrap(), "");
-    } 
+        if variance_x == 0.0 || variance_y == 0.0 {
+            return 0.0;
+        }
+        covariance / (variance_x * variance_y).sqrt()
+    }
 
-    #[test]
-    fn neighbours_in() {
-        let mut g: Graph<u32> = Graph::new();
-        g.add_vertex(1);
-        g.add_vertex(2);
-        g.add_vertex(3);
-        
-        g.add_edge(1,2).unwrap();
+    /// Returns the shortest directed cycle through the given vertex as a sequence of vertices
+    /// starting at the given vertex. The vertex is not repeated at the end of the sequence.
+    /// Returns None if there is no such cycle and NoSuchVertex GraphError, if vertex is not in graph
+    pub fn shortest_cycle_through(&self, vertex: &T) -> Result<Option<Vec<T>>, GraphError> {
+        if !self.vertices.contains(vertex) {
+            return Err(GraphError::NoSuchVertex);
+        }
 
-        assert_eq!(g.inbound_table.len(),1);
-        g.add_edge(1,3).unwrap();
-        let v: Vec<&u32> = g.out_neighbours(&1).collect();
-        assert_eq!(v.len(), 2);
+        let mut predecessors: HashMap<T, T> = HashMap::new();
+        let mut queue: VecDeque<T> = VecDeque::new();
+        queue.push_back(*vertex);
 
-        assert_eq!(v[0], &2);
+        while let Some(current) = queue.pop_front() {
+            for neighbour in self.out_neighbours(&current) {
+                if neighbour == vertex {
+                    // Walk back along the predecessors to reconstruct the cycle
+                    let mut cycle = vec![current];
+                    let mut step = current;
+                    while step != *vertex {
+                        step = predecessors[&step];
+                        cycle.push(step);
+                    }
+                    cycle.reverse();
+                    return Ok(Some(cycle));
+                }
+                if !predecessors.contains_key(neighbour) {
+                    predecessors.insert(*neighbour, current);
+                    queue.push_back(*neighbour);
+                }
+            }
+        }
+        Ok(None)
     }
 
-    #[test]
-    fn is_vertex_in_graph_given_vertex_in_graph_return_true() {
-        let mut g: Graph<u32> = Graph::new();
-        assert_eq!(g.number_of_vertices(), 0);
-        g.add_vertex(1);
-        g.add_vertex(2);
-        g.add_vertex(3);
-        assert_eq!(g.number_of_vertices(), 3);
+    /// Returns the edge betweenness of every edge, that is the sum over all ordered vertex pairs
+    /// of the fraction of shortest directed paths between them which traverse the edge
+    pub fn edge_betweenness(&self) -> HashMap<(T, T), f64> {
+        let mut betweenness: HashMap<(T, T), f64> = self.edges.iter()
+            .map(|edge| (*edge, 0.0))
+            .collect();
 
-        assert!(g.is_vertex_in_graph(&1));
-        assert!(g.is_vertex_in_graph(&2));
-        assert!(g.is_vertex_in_graph(&3));
-    }
+        for source in &self.vertices {
+            // Breadth first search counting the number of shortest paths to every vertex
+            let mut order: Vec<T> = Vec::new();
+            let mut predecessors: HashMap<T, Vec<T>> = HashMap::new();
+            let mut path_counts: HashMap<T, f64> = HashMap::new();
+            let mut distances: HashMap<T, usize> = HashMap::new();
+            let mut queue: VecDeque<T> = VecDeque::new();
+            path_counts.insert(*source, 1.0);
+            distances.insert(*source, 0);
+            queue.push_back(*source);
 
-    #[test]
-    fn is_vertex_in_graph_given_vertex_not_in_graph_return_false() {
-        let mut g: Graph<u32> = Graph::new();
-        assert_eq!(g.number_of_vertices(), 0);
-        g.add_vertex(1);
-        g.add_vertex(2);
-        g.add_vertex(3);
-        assert_eq!(g.number_of_vertices(), 3);
+            while let Some(current) = queue.pop_front() {
+                order.push(current);
+                let distance = distances[&current];
+                for neighbour in self.out_neighbours(&current) {
+                    if !distances.contains_key(neighbour) {
+                        distances.insert(*neighbour, distance + 1);
+                        queue.push_back(*neighbour);
+                    }
+                    if distances[neighbour] == distance + 1 {
+                        *path_counts.entry(*neighbour).or_insert(0.0) += path_counts[&current];
+                        predecessors.entry(*neighbour).or_default().push(current);
+                    }
+                }
+            }
 
-        assert!(!g.is_vertex_in_graph(&4));
-        assert!(!g.is_vertex_in_graph(&6));
-    } 
+            // Accumulate the dependencies in order of non-increasing distance from the source
+            let mut dependencies: HashMap<T, f64> = HashMap::new();
+            for vertex in order.iter().rev() {
+                let dependency = dependencies.get(vertex).copied().unwrap_or(0.0);
+                if let Some(preds) = predecessors.get(vertex) {
+                    for predecessor in preds {
+                        let contribution = path_counts[predecessor] / path_counts[vertex] * (1.0 + dependency);
+                        *betweenness.entry((*predecessor, *vertex)).or_insert(0.0) += contribution;
+                        *dependencies.entry(*predecessor).or_insert(0.0) += contribution;
+                    }
+                }
+            }
+        }
+        betweenness
+    }
 
-    #[test]
-    fn is_edge_in_graph_given_edge_in_graph_return_true() {
-        let mut g: Graph<u32> = Graph::new();
-        assert_eq!(g.number_of_vertices(), 0);
-        g.add_vertex(1);
-        g.add_vertex(2);
-        g.add_vertex(3);
-        g.add_edge(2, 3).unwrap();
-        g.add_edge(2, 1).unwrap();
-        g.add_edge(1, 2).unwrap();
-        assert_eq!(g.number_of_vertices(), 3);
+    /// Splits the graph into communities with the Girvan–Newman algorithm by repeatedly removing
+    /// the edge with the highest edge betweenness until the graph has at least
+    /// target_communities weakly connected components or no edges are left
+    pub fn girvan_newman(&self, target_communities: usize) -> Vec<HashSet<T>> where U: Clone {
+        let mut working = self.clone();
+        let mut components = working.weakly_connected_components();
+        while components.len() < target_communities && working.number_of_edges() > 0 {
+            let betweenness = working.edge_betweenness();
+            let (outbound, incoming) = betweenness.iter()
+                .max_by(|(_, a), (_, b)| a.total_cmp(b))
+                .map(|(edge, _)| *edge)
+                .unwrap();
+            working.detach_edge(&outbound, &incoming);
+            components = working.weakly_connected_components();
+        }
 
-        assert!(g.is_edge_in_graph(2, 3));
-        assert!(g.is_edge_in_graph(2, 1));
-        assert!(g.is_edge_in_graph(1, 2));
+        components.into_iter()
+            .map(|component| component.into_iter().collect())
+            .collect()
     }
 
-    #[test]
-    fn is_edge_in_graph_given_edge_not_in_graph_return_false() {
-        let mut g: Graph<u32> = Graph::new();
-        assert_eq!(g.number_of_vertices(), 0);
-        g.add_vertex(1);
-        g.add_vertex(2);
-        g.add_vertex(3);
-        g.add_edge(2, 3).unwrap();
-        g.add_edge(2, 1).unwrap();
-        g.add_edge(1, 2).unwrap();
-        assert_eq!(g.number_of_vertices(), 3);
+    /// Returns every vertex reachable from start with at most k outbound edges mapped to its
+    /// distance from start. The start vertex itself is included with distance 0.
+    /// Returns NoSuchVertex GraphError, if start is not in graph
+    pub fn k_hop_neighbours(&self, start: &T, k: usize) -> Result<HashMap<T, usize>, GraphError> {
+        if !self.vertices.contains(start) {
+            return Err(GraphError::NoSuchVertex);
+        }
 
-        assert!(!g.is_edge_in_graph(1, 3));
-        assert!(!g.is_edge_in_graph(1, 1));
-        assert!(!g.is_edge_in_graph(3, 1));
-    } 
+        let mut distances: HashMap<T, usize> = HashMap::new();
+        let mut queue: VecDeque<T> = VecDeque::new();
+        distances.insert(*start, 0);
+        queue.push_back(*start);
 
-    #[test]
-    fn set_label_given_vertex_in_graph_set_label() {
-        let mut g: Graph<u32> = Graph::new();
-        assert_eq!(g.number_of_vertices(), 0);
-        g.add_vertex_with_label(1, "A");
-        g.add_vertex_with_label(2, "B");
+        while let Some(current) = queue.pop_front() {
+            let distance = distances[&current];
+            if distance == k {
+                continue;
+            }
+            for neighbour in self.out_neighbours(&current) {
+                if !distances.contains_key(neighbour) {
+                    distances.insert(*neighbour, distance + 1);
+                    queue.push_back(*neighbour);
+                }
+            }
+        }
+        Ok(distances)
+    }
+
+    /// Clears target and copies the contents of this graph into it. The allocations of target
+    /// are reused where possible
+    pub fn copy_into(&self, target: &mut Graph<T, U>) where U: Clone {
+        target.vertices.clone_from(&self.vertices);
+        target.edges.clone_from(&self.edges);
+        target.vertex_labels.clone_from(&self.vertex_labels);
+        target.vertex_values.clone_from(&self.vertex_values);
+        target.edge_weights.clone_from(&self.edge_weights);
+        target.inbound_table.clone_from(&self.inbound_table);
+        target.outbound_table.clone_from(&self.outbound_table);
+        target.components.clone_from(&self.components);
+    }
+
+    /// Returns an iterator over the vertices whose degree in the undirected projection of the
+    /// graph is at least min
+    pub fn vertices_with_degree_at_least(&self, min: usize) -> impl Iterator<Item = &T> + '_ {
+        self.vertices.iter()
+            .filter(move |vertex| self.undirected_neighbours(vertex).len() >= min)
+    }
+
+    /// Returns the radius of the graph, that is the minimum eccentricity over all vertices where
+    /// the eccentricity of a vertex is the largest directed distance to any other vertex.
+    /// Returns None if the graph is empty or some vertex cannot reach all others
+    pub fn radius(&self) -> Option<usize> {
+        let mut radius: Option<usize> = None;
+        for vertex in &self.vertices {
+            let eccentricity = self.eccentricity_of(vertex)?;
+            radius = Some(radius.map_or(eccentricity, |r| r.min(eccentricity)));
+        }
+        radius
+    }
+
+    /// Returns the incidence matrix with one row per vertex in vertex_order and one column per
+    /// edge in edge_order. An entry is -1 if the vertex is the outbound end (tail) of the edge, +1
+    /// if it is the incoming end (head) and 0 otherwise. Self-loops and edges which are not in
+    /// the graph yield a column of zeros
+    pub fn incidence_matrix(&self, vertex_order: &[T], edge_order: &[(T, T)]) -> Vec<Vec<i8>> {
+        let mut matrix = vec![vec![0; edge_order.len()]; vertex_order.len()];
+        for (column, (outbound, incoming)) in edge_order.iter().enumerate() {
+            if outbound == incoming || !self.edges.contains(&(*outbound, *incoming)) {
+                continue;
+            }
+            for (row, vertex) in vertex_order.iter().enumerate() {
+                if vertex == outbound {
+                    matrix[row][column] = -1;
+                } else if vertex == incoming {
+                    matrix[row][column] = 1;
+                }
+            }
+        }
+        matrix
+    }
+
+    /// Returns a fixed order of the vertices together with the adjacency matrix in that order.
+    /// Entry (i, j) is true if there is an edge from the i-th to the j-th vertex, so self-loops
+    /// are on the diagonal
+    pub fn adjacency_matrix(&self) -> (Vec<T>, Vec<Vec<bool>>) {
+        let order: Vec<T> = self.vertices.iter().copied().collect();
+        let indices: HashMap<T, usize> = order.iter()
+            .enumerate()
+            .map(|(index, vertex)| (*vertex, index))
+            .collect();
+
+        let mut matrix = vec![vec![false; order.len()]; order.len()];
+        for (outbound, incoming) in &self.edges {
+            matrix[indices[outbound]][indices[incoming]] = true;
+        }
+        (order, matrix)
+    }
+
+    /// Returns the number of edges whose (outbound, incoming) endpoints satisfy the predicate
+    pub fn count_edges_where<F: Fn(&T, &T) -> bool>(&self, pred: F) -> usize {
+        self.edges.iter()
+            .filter(|(outbound, incoming)| pred(outbound, incoming))
+            .count()
+    }
+
+    /// Returns the Adamic–Adar index of two vertices, that is the sum of 1 / ln(degree) over
+    /// their common outbound neighbours where degree is taken in the undirected projection.
+    /// Common neighbours with degree of at most 1 are skipped.
+    /// Returns NoSuchVertex GraphError, if a or b is not in graph
+    pub fn adamic_adar(&self, a: &T, b: &T) -> Result<f64, GraphError> {
+        if !self.vertices.contains(a) || !self.vertices.contains(b) {
+            return Err(GraphError::NoSuchVertex);
+        }
+
+        let outbound_of_b: HashSet<&T> = self.out_neighbours(b).collect();
+        let common: HashSet<&T> = self.out_neighbours(a)
+            .filter(|neighbour| outbound_of_b.contains(neighbour))
+            .collect();
+
+        Ok(common.into_iter()
+            .map(|neighbour| self.undirected_neighbours(neighbour).len())
+            .filter(|degree| *degree > 1)
+            .map(|degree| 1.0 / (degree as f64).ln())
+            .sum())
+    }
+
+    /// Returns the number of weakly connected components. The count is maintained incrementally
+    /// while vertices and edges are added and rebuilt once after vertices or edges were removed
+    pub fn component_count(&self) -> usize {
+        self.components.get_or_init(|| {
+            let mut components = UnionFind::new();
+            for vertex in &self.vertices {
+                components.insert(*vertex);
+            }
+            for (outbound, incoming) in &self.edges {
+                components.union(*outbound, *incoming);
+            }
+            components
+        }).count()
+    }
+
+    /// Returns the vertex with the highest number of outbound neighbours together with that
+    /// number or None if the graph is empty. Ties are broken arbitrarily
+    pub fn max_out_degree_vertex(&self) -> Option<(T, usize)> {
+        self.vertices.iter()
+            .map(|vertex| (*vertex, self.out_neighbours(vertex).count()))
+            .max_by_key(|(_, degree)| *degree)
+    }
+
+    /// Returns the vertices reachable from start in breadth first order where the outbound
+    /// neighbours of each vertex are visited in ascending order, making the order deterministic.
+    /// Returns NoSuchVertex GraphError, if start is not in graph
+    pub fn bfs_ordered(&self, start: &T) -> Result<Vec<T>, GraphError> where T: Ord {
+        if !self.vertices.contains(start) {
+            return Err(GraphError::NoSuchVertex);
+        }
+
+        let mut order: Vec<T> = Vec::new();
+        let mut visited: HashSet<T> = HashSet::new();
+        let mut queue: VecDeque<T> = VecDeque::new();
+        visited.insert(*start);
+        queue.push_back(*start);
+
+        while let Some(current) = queue.pop_front() {
+            order.push(current);
+            let mut neighbours: Vec<T> = self.out_neighbours(&current).copied().collect();
+            neighbours.sort();
+            for neighbour in neighbours {
+                if visited.insert(neighbour) {
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+        Ok(order)
+    }
+
+    /// Returns a set of edges whose removal makes the graph acyclic. The edges are the back edges
+    /// found by a depth first search over all vertices, so the set is not necessarily minimal
+    pub fn greedy_feedback_edge_set(&self) -> Vec<(T, T)> {
+        let mut back_edges: Vec<(T, T)> = Vec::new();
+        let mut on_stack: HashSet<T> = HashSet::new();
+        let mut finished: HashSet<T> = HashSet::new();
+
+        for root in &self.vertices {
+            if finished.contains(root) {
+                continue;
+            }
+            // Stack of vertices together with the index of the next outbound neighbour to visit
+            let mut stack: Vec<(T, usize)> = vec![(*root, 0)];
+            on_stack.insert(*root);
+
+            while let Some((current, index)) = stack.pop() {
+                match self.out_neighbours(&current).nth(index) {
+                    Some(neighbour) => {
+                        stack.push((current, index + 1));
+                        if on_stack.contains(neighbour) {
+                            back_edges.push((current, *neighbour));
+                        } else if !finished.contains(neighbour) {
+                            on_stack.insert(*neighbour);
+                            stack.push((*neighbour, 0));
+                        }
+                    },
+                    None => {
+                        on_stack.remove(&current);
+                        finished.insert(current);
+                    }
+                }
+            }
+        }
+        back_edges
+    }
+
+    /// Returns a fixed order of the vertices together with one packed bitset per vertex in that
+    /// order. Bit j of the bitset of vertex i is set if the j-th vertex can be reached from the
+    /// i-th vertex using at least one edge, so a vertex only reaches itself if it lies on a cycle
+    pub fn reachability_bitsets(&self) -> (Vec<T>, Vec<Vec<u64>>) {
+        let order: Vec<T> = self.vertices.iter().copied().collect();
+        let indices: HashMap<T, usize> = order.iter()
+            .enumerate()
+            .map(|(index, vertex)| (*vertex, index))
+            .collect();
+        let words = order.len().div_ceil(64);
+
+        let bitsets = order.iter()
+            .map(|start| {
+                let mut bitset = vec![0u64; words];
+                let mut stack: Vec<T> = self.out_neighbours(start).copied().collect();
+                while let Some(current) = stack.pop() {
+                    let index = indices[&current];
+                    if bitset[index / 64] & (1 << (index % 64)) != 0 {
+                        continue;
+                    }
+                    bitset[index / 64] |= 1 << (index % 64);
+                    stack.extend(self.out_neighbours(&current));
+                }
+                bitset
+            })
+            .collect();
+        (order, bitsets)
+    }
+
+    /// Returns an iterator over the outbound neighbours of the given vertex together with the
+    /// weight of the corresponding edge, or None for edges without a weight
+    pub fn weighted_out_edges(&self, vertex: &T) -> impl Iterator<Item = (T, Option<&u64>)> + '_ {
+        let vertex = *vertex;
+        self.out_neighbours(&vertex)
+            .map(move |neighbour| (*neighbour, self.edge_weights.get(&(vertex, *neighbour))))
+    }
+
+    /// Returns the labelled vertices grouped by their label. Vertices without a label are skipped
+    pub fn group_by_label(&self) -> HashMap<String, Vec<T>> {
+        let mut groups: HashMap<String, Vec<T>> = HashMap::new();
+        for (vertex, label) in &self.vertex_labels {
+            groups.entry(label.clone()).or_default().push(*vertex);
+        }
+        groups
+    }
+
+    /// Returns the fraction of pairs of neighbours of the vertex which are connected in the
+    /// undirected projection of the graph. Returns 0.0 for vertices with fewer than two neighbours
+    /// and NoSuchVertex GraphError, if vertex is not in graph
+    pub fn neighbourhood_density(&self, vertex: &T) -> Result<f64, GraphError> {
+        if !self.vertices.contains(vertex) {
+            return Err(GraphError::NoSuchVertex);
+        }
+
+        let neighbours: Vec<T> = self.undirected_neighbours(vertex).into_iter().collect();
+        let k = neighbours.len();
+        if k < 2 {
+            return Ok(0.0);
+        }
+        Ok(self.connected_pairs(&neighbours) as f64 / (k * (k - 1) / 2) as f64)
+    }
+
+    /// Returns the set of vertices which are reachable from the given vertex and from which the
+    /// given vertex is reachable, i.e. its strongly connected component including itself.
+    /// Returns NoSuchVertex GraphError, if vertex is not in graph
+    pub fn connected_to(&self, vertex: &T) -> Result<HashSet<T>, GraphError> {
+        if !self.vertices.contains(vertex) {
+            return Err(GraphError::NoSuchVertex);
+        }
+
+        let forward = self.bfs_distances(vertex);
+        Ok(self.reaching(vertex).into_iter()
+            .filter(|other| forward.contains_key(other))
+            .collect())
+    }
+
+    /// Returns the number of edges of the longest simple path starting at start which was found by
+    /// a depth first search over all simple paths. Since there can be exponentially many simple
+    /// paths, the search stops after max_explore path extensions, so the result is a lower bound
+    /// and only exact if the search finished within the budget.
+    /// Returns NoSuchVertex GraphError, if start is not in graph
+    pub fn longest_simple_path_len(&self, start: &T, max_explore: usize) -> Result<usize, GraphError> {
+        if !self.vertices.contains(start) {
+            return Err(GraphError::NoSuchVertex);
+        }
+
+        let mut longest = 0;
+        let mut explored = 0;
+        let mut on_path: HashSet<T> = HashSet::new();
+        // Stack of path vertices together with the index of the next outbound neighbour to try
+        let mut path: Vec<(T, usize)> = vec![(*start, 0)];
+        on_path.insert(*start);
+
+        while let Some((current, index)) = path.pop() {
+            match self.out_neighbours(&current).nth(index) {
+                Some(neighbour) => {
+                    path.push((current, index + 1));
+                    if on_path.contains(neighbour) || explored >= max_explore {
+                        continue;
+                    }
+                    explored += 1;
+                    on_path.insert(*neighbour);
+                    path.push((*neighbour, 0));
+                    longest = longest.max(path.len() - 1);
+                },
+                None => {
+                    on_path.remove(&current);
+                }
+            }
+        }
+        Ok(longest)
+    }
+
+    /// Returns the number of triangles every vertex is part of in the undirected projection of
+    /// the graph
+    pub fn triangle_counts(&self) -> HashMap<T, usize> {
+        self.vertices.iter()
+            .map(|vertex| {
+                let neighbours: Vec<T> = self.undirected_neighbours(vertex).into_iter().collect();
+                (*vertex, self.connected_pairs(&neighbours))
+            })
+            .collect()
+    }
+
+    /// Returns the directed distance from the nearest source to every vertex reachable from any of
+    /// the sources, where the sources themselves have distance 0.
+    /// Returns NoSuchVertex GraphError, if any source is not in graph
+    pub fn multi_source_bfs_distances(&self, sources: &[T]) -> Result<HashMap<T, usize>, GraphError> {
+        if sources.iter().any(|source| !self.vertices.contains(source)) {
+            return Err(GraphError::NoSuchVertex);
+        }
+
+        let mut distances: HashMap<T, usize> = HashMap::new();
+        let mut queue: VecDeque<T> = VecDeque::new();
+        for source in sources {
+            if distances.insert(*source, 0).is_none() {
+                queue.push_back(*source);
+            }
+        }
+
+        while let Some(current) = queue.pop_front() {
+            let distance = distances[&current];
+            for neighbour in self.out_neighbours(&current) {
+                if !distances.contains_key(neighbour) {
+                    distances.insert(*neighbour, distance + 1);
+                    queue.push_back(*neighbour);
+                }
+            }
+        }
+        Ok(distances)
+    }
+
+    /// Returns whether every vertex and every edge of this graph is also in the other graph
+    pub fn is_subgraph_of(&self, other: &Graph<T, U>) -> bool {
+        self.vertices.is_subset(&other.vertices) && self.edges.is_subset(&other.edges)
+    }
+
+    /// Returns the diameter of every weakly connected component, that is the largest distance
+    /// between two of its vertices when ignoring edge directions. The order of the components is
+    /// unspecified
+    pub fn component_diameters(&self) -> Vec<usize> {
+        self.weakly_connected_components().iter()
+            .map(|component| component.iter()
+                .filter_map(|vertex| self.undirected_bfs_distances(vertex).into_values().max())
+                .max()
+                .unwrap_or(0))
+            .collect()
+    }
+
+    /// Returns the undirected projection of the graph in the METIS graph format. The vertices are
+    /// numbered by their 1-based position in order and neighbours which are not in order as well as
+    /// self-loops are omitted. The header line contains the number of vertices and undirected
+    /// edges, followed by one line per vertex with its ascending neighbour numbers
+    pub fn to_metis(&self, order: &[T]) -> String {
+        let indices: HashMap<T, usize> = order.iter()
+            .enumerate()
+            .map(|(index, vertex)| (*vertex, index + 1))
+            .collect();
+
+        let mut lines: Vec<String> = Vec::new();
+        let mut degree_sum = 0;
+        for vertex in order {
+            let mut neighbours: Vec<usize> = self.undirected_neighbours(vertex).iter()
+                .filter_map(|neighbour| indices.get(neighbour).copied())
+                .collect();
+            neighbours.sort();
+            degree_sum += neighbours.len();
+            lines.push(neighbours.iter().map(|index| index.to_string()).collect::<Vec<String>>().join(" "));
+        }
+
+        let mut metis = format!("{} {}\n", order.len(), degree_sum / 2);
+        for line in lines {
+            metis.push_str(&line);
+            metis.push('\n');
+        }
+        metis
+    }
+
+    /// Returns the eccentricity of every vertex, that is the largest directed distance to any
+    /// other vertex, or None for vertices which cannot reach all others
+    pub fn all_eccentricities(&self) -> HashMap<T, Option<usize>> {
+        self.vertices.iter()
+            .map(|vertex| (*vertex, self.eccentricity_of(vertex)))
+            .collect()
+    }
+
+    /// Returns the set of vertices which are reachable from start using at least one edge, so
+    /// start is only contained if it lies on a cycle.
+    /// Returns NoSuchVertex GraphError, if start is not in graph
+    pub fn reachable_from(&self, start: &T) -> Result<HashSet<T>, GraphError> {
+        if !self.vertices.contains(start) {
+            return Err(GraphError::NoSuchVertex);
+        }
+
+        let mut reachable: HashSet<T> = HashSet::new();
+        let mut stack: Vec<T> = self.out_neighbours(start).copied().collect();
+        while let Some(current) = stack.pop() {
+            if reachable.insert(current) {
+                stack.extend(self.out_neighbours(&current).copied());
+            }
+        }
+        Ok(reachable)
+    }
+
+    /// Returns the number of vertices which are reachable from the given vertex, excluding the
+    /// vertex itself. Returns NoSuchVertex GraphError, if vertex is not in graph
+    pub fn descendant_count(&self, vertex: &T) -> Result<usize, GraphError> {
+        if !self.vertices.contains(vertex) {
+            return Err(GraphError::NoSuchVertex);
+        }
+        Ok(self.bfs_distances(vertex).len() - 1)
+    }
+
+    /// Returns the number of vertices from which the given vertex can be reached, excluding the
+    /// vertex itself. Returns NoSuchVertex GraphError, if vertex is not in graph
+    pub fn ancestor_count(&self, vertex: &T) -> Result<usize, GraphError> {
+        if !self.vertices.contains(vertex) {
+            return Err(GraphError::NoSuchVertex);
+        }
+        Ok(self.reaching(vertex).len() - 1)
+    }
+
+    /// Returns the lexicographically smallest topological order of the vertices, using Kahn's
+    /// algorithm which always picks the smallest vertex without remaining inbound edges.
+    /// Returns CycleDetected GraphError, if the graph contains a cycle
+    pub fn lexicographic_topological_sort(&self) -> Result<Vec<T>, GraphError> where T: Ord {
+        let mut in_degrees = self.in_degrees();
+        let mut ready: BinaryHeap<Reverse<T>> = in_degrees.iter()
+            .filter(|(_, degree)| **degree == 0)
+            .map(|(vertex, _)| Reverse(*vertex))
+            .collect();
+
+        let mut order: Vec<T> = Vec::new();
+        while let Some(Reverse(current)) = ready.pop() {
+            order.push(current);
+            for neighbour in self.out_neighbours(&current) {
+                let degree = in_degrees.get_mut(neighbour).unwrap();
+                *degree -= 1;
+                if *degree == 0 {
+                    ready.push(Reverse(*neighbour));
+                }
+            }
+        }
+
+        if order.len() < self.vertices.len() {
+            return Err(GraphError::CycleDetected);
+        }
+        Ok(order)
+    }
+
+    /// Returns whether the sequence of vertices is a walk in the graph, i.e. all vertices are in
+    /// the graph and there is an edge between every pair of consecutive vertices. Vertices may
+    /// repeat and an empty sequence is always a walk
+    pub fn contains_walk(&self, sequence: &[T]) -> bool {
+        sequence.iter().all(|vertex| self.vertices.contains(vertex))
+            && sequence.windows(2).all(|pair| self.edges.contains(&(pair[0], pair[1])))
+    }
+
+    /// Returns the number of edges for every pair of (outbound label, incoming label). Vertices
+    /// without a label are counted with the empty string as label
+    pub fn edge_counts_by_label(&self) -> HashMap<(String, String), usize> {
+        let mut counts: HashMap<(String, String), usize> = HashMap::new();
+        for (outbound, incoming) in &self.edges {
+            let outbound_label = self.vertex_labels.get(outbound).cloned().unwrap_or_default();
+            let incoming_label = self.vertex_labels.get(incoming).cloned().unwrap_or_default();
+            *counts.entry((outbound_label, incoming_label)).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Returns whether removing the vertex would increase the number of weakly connected
+    /// components. Returns NoSuchVertex GraphError, if vertex is not in graph
+    pub fn is_cut_vertex(&self, vertex: &T) -> Result<bool, GraphError> {
+        if !self.vertices.contains(vertex) {
+            return Err(GraphError::NoSuchVertex);
+        }
+
+        // The vertex is a cut vertex iff its neighbours are not all connected without it
+        let neighbours = self.undirected_neighbours(vertex);
+        let first = match neighbours.iter().next() {
+            Some(first) => *first,
+            None => return Ok(false),
+        };
+
+        let mut visited: HashSet<T> = HashSet::new();
+        visited.insert(*vertex);
+        visited.insert(first);
+        let mut stack = vec![first];
+        while let Some(current) = stack.pop() {
+            for neighbour in self.undirected_neighbours(&current) {
+                if visited.insert(neighbour) {
+                    stack.push(neighbour);
+                }
+            }
+        }
+        Ok(!neighbours.iter().all(|neighbour| visited.contains(neighbour)))
+    }
+
+    /// Returns the value of a maximum flow from source to sink using the Edmonds–Karp algorithm.
+    /// Edge weights are used as capacities and edges without a weight have capacity 1. The flow
+    /// from a vertex to itself is 0 and flows larger than u64::MAX are reported as u64::MAX.
+    /// Returns NoSuchVertex GraphError, if source or sink is not in graph
+    pub fn max_flow(&self, source: &T, sink: &T) -> Result<u64, GraphError> {
+        if !self.vertices.contains(source) || !self.vertices.contains(sink) {
+            return Err(GraphError::NoSuchVertex);
+        }
+        let (flow, _) = self.edmonds_karp(source, sink, |edge| *self.edge_weights.get(edge).unwrap_or(&1));
+        Ok(flow)
+    }
+
+    /// Returns the value of a minimum cut between source and sink together with the cut edges, i.e.
+    /// the edges leading from the vertices still reachable from source in the residual graph of a
+    /// maximum flow to the remaining vertices. Capacities are determined as in max_flow and cut
+    /// values larger than u64::MAX are reported as u64::MAX.
+    /// Returns NoSuchVertex GraphError, if source or sink is not in graph
+    pub fn min_cut(&self, source: &T, sink: &T) -> Result<(u64, Vec<(T, T)>), GraphError> {
+        if !self.vertices.contains(source) || !self.vertices.contains(sink) {
+            return Err(GraphError::NoSuchVertex);
+        }
+        let (flow, residual) = self.edmonds_karp(source, sink, |edge| *self.edge_weights.get(edge).unwrap_or(&1));
+
+        let mut source_side: HashSet<T> = HashSet::new();
+        source_side.insert(*source);
+        let mut stack = vec![*source];
+        while let Some(current) = stack.pop() {
+            for neighbour in self.undirected_neighbours(&current) {
+                if residual[&(current, neighbour)] > 0 && source_side.insert(neighbour) {
+                    stack.push(neighbour);
+                }
+            }
+        }
+
+        let cut_edges = self.edges.iter()
+            .filter(|(outbound, incoming)| source_side.contains(outbound) && !source_side.contains(incoming))
+            .copied()
+            .collect();
+        Ok((flow, cut_edges))
+    }
+
+    /// Returns the minimum number of edges whose removal disconnects b from a, i.e. the maximum
+    /// flow from a to b where every edge has capacity 1. Weights are ignored.
+    /// Returns NoSuchVertex GraphError, if a or b is not in graph
+    pub fn edge_connectivity(&self, a: &T, b: &T) -> Result<usize, GraphError> {
+        if !self.vertices.contains(a) || !self.vertices.contains(b) {
+            return Err(GraphError::NoSuchVertex);
+        }
+        let (flow, _) = self.edmonds_karp(a, b, |_| 1);
+        Ok(flow as usize)
+    }
+
+    /// Returns the number of automorphisms of the graph, i.e. permutations of the vertices which
+    /// map the set of edges onto itself. Labels and weights are ignored. All n! permutations are
+    /// enumerated, so this is only feasible for small graphs
+    pub fn automorphism_count(&self) -> usize {
+        let vertices: Vec<T> = self.vertices.iter().copied().collect();
+        let mut permutation = vertices.clone();
+        let preserves_edges = |permutation: &[T]| {
+            let mapping: HashMap<T, T> = vertices.iter().copied().zip(permutation.iter().copied()).collect();
+            self.edges.iter()
+                .all(|(outbound, incoming)| self.edges.contains(&(mapping[outbound], mapping[incoming])))
+        };
+
+        // Enumerate all permutations with Heap's algorithm
+        let n = permutation.len();
+        let mut count = usize::from(preserves_edges(&permutation));
+        let mut counters = vec![0; n];
+        let mut i = 0;
+        while i < n {
+            if counters[i] < i {
+                if i % 2 == 0 {
+                    permutation.swap(0, i);
+                } else {
+                    permutation.swap(counters[i], i);
+                }
+                if preserves_edges(&permutation) {
+                    count += 1;
+                }
+                counters[i] += 1;
+                i = 0;
+            } else {
+                counters[i] = 0;
+                i += 1;
+            }
+        }
+        count
+    }
+
+    /// Performs up to attempts random double edge swaps, each rewiring two edges (a, b) and (c, d)
+    /// into (a, d) and (c, b). Swaps which would create a self-loop or an edge that already exists
+    /// are rejected, so the in- and out-degree of every vertex is preserved. Weights of rewired
+    /// edges are dropped. Returns the number of successful swaps
+    pub fn double_edge_swap(&mut self, rng: &mut impl Rng, attempts: usize) -> usize {
+        let mut edges: Vec<(T, T)> = self.edges.iter().copied().collect();
+        if edges.len() < 2 {
+            return 0;
+        }
+
+        let mut swaps = 0;
+        for _ in 0..attempts {
+            let i = rng.gen_range(0..edges.len());
+            let j = rng.gen_range(0..edges.len());
+            let (a, b) = edges[i];
+            let (c, d) = edges[j];
+            if a == c || b == d || a == d || c == b
+                || self.edges.contains(&(a, d)) || self.edges.contains(&(c, b)) {
+                continue;
+            }
+
+            self.detach_edge(&a, &b);
+            self.detach_edge(&c, &d);
+            let _ = self.add_edge(a, d);
+            let _ = self.add_edge(c, b);
+            edges[i] = (a, d);
+            edges[j] = (c, b);
+            swaps += 1;
+        }
+        swaps
+    }
+
+    /// Returns the vertices reachable from start in the order in which a breadth first search
+    /// over the outbound edges visits them, starting with start itself.
+    /// Returns NoSuchVertex GraphError, if start is not in graph
+    pub fn bfs(&self, start: &T) -> Result<Vec<T>, GraphError> {
+        if !self.vertices.contains(start) {
+            return Err(GraphError::NoSuchVertex);
+        }
+
+        let mut order: Vec<T> = Vec::new();
+        let mut visited: HashSet<T> = HashSet::new();
+        let mut queue: VecDeque<T> = VecDeque::new();
+        visited.insert(*start);
+        queue.push_back(*start);
+
+        while let Some(current) = queue.pop_front() {
+            order.push(current);
+            for neighbour in self.out_neighbours(&current) {
+                if visited.insert(*neighbour) {
+                    queue.push_back(*neighbour);
+                }
+            }
+        }
+        Ok(order)
+    }
+
+    /// Returns the vertices reachable from start in the order in which a depth first search over
+    /// the outbound edges discovers them, starting with start itself. The search uses an explicit
+    /// stack, so deep graphs do not overflow the call stack.
+    /// Returns NoSuchVertex GraphError, if start is not in graph
+    pub fn dfs(&self, start: &T) -> Result<Vec<T>, GraphError> {
+        if !self.vertices.contains(start) {
+            return Err(GraphError::NoSuchVertex);
+        }
+
+        let mut order: Vec<T> = vec![*start];
+        let mut visited: HashSet<T> = HashSet::new();
+        visited.insert(*start);
+        // Stack of vertices together with the index of the next outbound neighbour to visit
+        let mut stack: Vec<(T, usize)> = vec![(*start, 0)];
+
+        while let Some((current, index)) = stack.pop() {
+            if let Some(neighbour) = self.out_neighbours(&current).nth(index) {
+                stack.push((current, index + 1));
+                if visited.insert(*neighbour) {
+                    order.push(*neighbour);
+                    stack.push((*neighbour, 0));
+                }
+            }
+        }
+        Ok(order)
+    }
+
+    /// Returns a spanning forest of the graph, i.e. a subgraph with all vertices, labels and values
+    /// which contains one spanning tree of the undirected projection per weakly connected
+    /// component. Retained edges keep their direction and weight
+    pub fn spanning_forest(&self) -> Graph<T, U> where U: Clone {
+        let mut forest: Graph<T, U> = Graph::new();
+        forest.vertices.clone_from(&self.vertices);
+        forest.vertex_labels.clone_from(&self.vertex_labels);
+        forest.vertex_values.clone_from(&self.vertex_values);
+        forest.components = OnceLock::new();
+
+        let mut components: UnionFind<T> = UnionFind::new();
+        for (outbound, incoming) in &self.edges {
+            if components.union(*outbound, *incoming) {
+                let _ = forest.add_edge(*outbound, *incoming);
+                if let Some(weight) = self.edge_weights.get(&(*outbound, *incoming)) {
+                    forest.edge_weights.insert((*outbound, *incoming), *weight);
+                }
+            }
+        }
+        forest
+    }
+
+    /// Returns all sources, i.e. vertices without inbound edges, and all sinks, i.e. vertices
+    /// without outbound edges, as (sources, sinks). Isolated vertices appear in both
+    pub fn terminals(&self) -> (Vec<T>, Vec<T>) {
+        let mut sources: Vec<T> = Vec::new();
+        let mut sinks: Vec<T> = Vec::new();
+        for vertex in &self.vertices {
+            if !self.inbound_table.contains_key(vertex) {
+                sources.push(*vertex);
+            }
+            if !self.outbound_table.contains_key(vertex) {
+                sinks.push(*vertex);
+            }
+        }
+        (sources, sinks)
+    }
+
+    /// Returns a path with the fewest edges from start to goal as sequence of vertices including
+    /// both endpoints, or None if goal is not reachable from start. If start and goal are equal
+    /// the path only consists of start.
+    /// Returns NoSuchVertex GraphError, if start or goal is not in graph
+    pub fn shortest_path(&self, start: &T, goal: &T) -> Result<Option<Vec<T>>, GraphError> {
+        if !self.vertices.contains(start) || !self.vertices.contains(goal) {
+            return Err(GraphError::NoSuchVertex);
+        }
+
+        let mut predecessors: HashMap<T, T> = HashMap::new();
+        let mut queue: VecDeque<T> = VecDeque::new();
+        predecessors.insert(*start, *start);
+        queue.push_back(*start);
+
+        while let Some(current) = queue.pop_front() {
+            if current == *goal {
+                let mut path = vec![current];
+                let mut step = current;
+                while step != *start {
+                    step = predecessors[&step];
+                    path.push(step);
+                }
+                path.reverse();
+                return Ok(Some(path));
+            }
+            for neighbour in self.out_neighbours(&current) {
+                if let Entry::Vacant(entry) = predecessors.entry(*neighbour) {
+                    entry.insert(current);
+                    queue.push_back(*neighbour);
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns the number of edges on a shortest path from start to goal, which is 0 if start and
+    /// goal are equal, or None if goal is not reachable from start.
+    /// Returns NoSuchVertex GraphError, if start or goal is not in graph
+    pub fn shortest_path_length(&self, start: &T, goal: &T) -> Result<Option<usize>, GraphError> {
+        if !self.vertices.contains(start) || !self.vertices.contains(goal) {
+            return Err(GraphError::NoSuchVertex);
+        }
+        Ok(self.bfs_distances(start).get(goal).copied())
+    }
+
+    /// Returns whether goal is reachable from start via directed edges. The search stops as soon
+    /// as goal is found and a vertex always reaches itself.
+    /// Returns NoSuchVertex GraphError, if start or goal is not in graph
+    pub fn has_path(&self, start: &T, goal: &T) -> Result<bool, GraphError> {
+        if !self.vertices.contains(start) || !self.vertices.contains(goal) {
+            return Err(GraphError::NoSuchVertex);
+        }
+
+        let mut visited: HashSet<T> = HashSet::new();
+        let mut stack = vec![*start];
+        visited.insert(*start);
+        while let Some(current) = stack.pop() {
+            if current == *goal {
+                return Ok(true);
+            }
+            for neighbour in self.out_neighbours(&current) {
+                if visited.insert(*neighbour) {
+                    stack.push(*neighbour);
+                }
+            }
+        }
+        Ok(false)
+    }
+
+    /// Returns a path of minimum total weight from start to goal as sequence of vertices including
+    /// both endpoints together with its cost, or None if goal is not reachable from start. Edges
+    /// without a weight count as weight 1. Paths whose cost would exceed u64::MAX are ignored.
+    /// Returns NoSuchVertex GraphError, if start or goal is not in graph
+    pub fn dijkstra(&self, start: &T, goal: &T) -> Result<Option<(Vec<T>, u64)>, GraphError> {
+        if !self.vertices.contains(start) || !self.vertices.contains(goal) {
+            return Err(GraphError::NoSuchVertex);
+        }
+
+        // T is not required to be Ord, so the queue refers to vertices by their position in pushed
+        let mut pushed: Vec<T> = vec![*start];
+        let mut queue: BinaryHeap<Reverse<(u64, usize)>> = BinaryHeap::new();
+        let mut costs: HashMap<T, u64> = HashMap::new();
+        let mut predecessors: HashMap<T, T> = HashMap::new();
+        let mut settled: HashSet<T> = HashSet::new();
+        costs.insert(*start, 0);
+        queue.push(Reverse((0, 0)));
+
+        while let Some(Reverse((cost, index))) = queue.pop() {
+            let current = pushed[index];
+            if !settled.insert(current) {
+                continue;
+            }
+            if current == *goal {
+                let mut path = vec![current];
+                let mut step = current;
+                while step != *start {
+                    step = predecessors[&step];
+                    path.push(step);
+                }
+                path.reverse();
+                return Ok(Some((path, cost)));
+            }
+            for neighbour in self.out_neighbours(&current) {
+                let weight = *self.edge_weights.get(&(current, *neighbour)).unwrap_or(&1);
+                let next_cost = match cost.checked_add(weight) {
+                    Some(next_cost) => next_cost,
+                    None => continue,
+                };
+                if costs.get(neighbour).is_none_or(|known| next_cost < *known) {
+                    costs.insert(*neighbour, next_cost);
+                    predecessors.insert(*neighbour, current);
+                    pushed.push(*neighbour);
+                    queue.push(Reverse((next_cost, pushed.len() - 1)));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns the number of edges which have to be removed to isolate the vertex, i.e. the number
+    /// of its inbound and outbound edges where a self-loop counts once. This is an upper bound on
+    /// the local edge connectivity of the vertex to any other vertex.
+    /// Returns NoSuchVertex GraphError, if vertex is not in graph
+    pub fn min_edges_to_isolate(&self, vertex: &T) -> Result<usize, GraphError> {
+        if !self.vertices.contains(vertex) {
+            return Err(GraphError::NoSuchVertex);
+        }
+        let self_loops = usize::from(self.edges.contains(&(*vertex, *vertex)));
+        Ok(self.out_neighbours(vertex).len() + self.in_neighbours(vertex).len() - self_loops)
+    }
+
+    /// Returns the vertices in a topological order using Kahn's algorithm, so every edge leads from
+    /// an earlier to a later vertex. Returns CycleDetected GraphError, if the graph contains a cycle
+    pub fn topological_sort(&self) -> Result<Vec<T>, GraphError> {
+        let mut in_degrees = self.in_degrees();
+        let mut ready: VecDeque<T> = in_degrees.iter()
+            .filter(|(_, degree)| **degree == 0)
+            .map(|(vertex, _)| *vertex)
+            .collect();
+
+        let mut order: Vec<T> = Vec::new();
+        while let Some(current) = ready.pop_front() {
+            order.push(current);
+            for neighbour in self.out_neighbours(&current) {
+                let degree = in_degrees.get_mut(neighbour).unwrap();
+                *degree -= 1;
+                if *degree == 0 {
+                    ready.push_back(*neighbour);
+                }
+            }
+        }
+
+        if order.len() < self.vertices.len() {
+            return Err(GraphError::CycleDetected);
+        }
+        Ok(order)
+    }
+
+    /// Returns a (layer, position) coordinate for every vertex where the layer is the index of the
+    /// topological generation of the vertex, i.e. the length of the longest path ending in it, and
+    /// the position is its index within that layer. The order within a layer is unspecified.
+    /// Returns CycleDetected GraphError, if the graph contains a cycle
+    pub fn layered_layout(&self) -> Result<HashMap<T, (usize, usize)>, GraphError> {
+        let mut in_degrees = self.in_degrees();
+        let mut layer: Vec<T> = in_degrees.iter()
+            .filter(|(_, degree)| **degree == 0)
+            .map(|(vertex, _)| *vertex)
+            .collect();
+
+        let mut coordinates: HashMap<T, (usize, usize)> = HashMap::new();
+        let mut layer_index = 0;
+        while !layer.is_empty() {
+            let mut next_layer: Vec<T> = Vec::new();
+            for (position, current) in layer.iter().enumerate() {
+                coordinates.insert(*current, (layer_index, position));
+                for neighbour in self.out_neighbours(current) {
+                    let degree = in_degrees.get_mut(neighbour).unwrap();
+                    *degree -= 1;
+                    if *degree == 0 {
+                        next_layer.push(*neighbour);
+                    }
+                }
+            }
+            layer = next_layer;
+            layer_index += 1;
+        }
+
+        if coordinates.len() < self.vertices.len() {
+            return Err(GraphError::CycleDetected);
+        }
+        Ok(coordinates)
+    }
+
+    /// Returns the transitivity of the undirected projection of the graph, that is three times the
+    /// number of triangles divided by the number of connected triples. Returns 0.0 if there are
+    /// no connected triples
+    pub fn transitivity(&self) -> f64 {
+        let connected_triples: usize = self.vertices.iter()
+            .map(|vertex| self.undirected_neighbours(vertex).len())
+            .map(|degree| degree * degree.saturating_sub(1) / 2)
+            .sum();
+        if connected_triples == 0 {
+            return 0.0;
+        }
+
+        // Every triangle is counted once at each of its three vertices
+        let triangle_corners: usize = self.triangle_counts().values().sum();
+        triangle_corners as f64 / connected_triples as f64
+    }
+
+    /// Returns whether the graph contains a directed cycle, including self-loops. Uses a depth
+    /// first search from every unvisited vertex where vertices are marked grey while they are on
+    /// the stack and black once finished, so an edge to a grey vertex closes a cycle
+    pub fn has_cycle(&self) -> bool {
+        // Vertices without a colour are white, i.e. not yet visited
+        let mut colours: HashMap<T, Colour> = HashMap::new();
+
+        for root in &self.vertices {
+            if colours.contains_key(root) {
+                continue;
+            }
+            // Stack of vertices together with the index of the next outbound neighbour to visit
+            let mut stack: Vec<(T, usize)> = vec![(*root, 0)];
+            colours.insert(*root, Colour::Grey);
+
+            while let Some((current, index)) = stack.pop() {
+                match self.out_neighbours(&current).nth(index) {
+                    Some(neighbour) => {
+                        stack.push((current, index + 1));
+                        match colours.get(neighbour) {
+                            Some(Colour::Grey) => return true,
+                            Some(Colour::Black) => {},
+                            None => {
+                                colours.insert(*neighbour, Colour::Grey);
+                                stack.push((*neighbour, 0));
+                            }
+                        }
+                    },
+                    None => {
+                        colours.insert(current, Colour::Black);
+                    }
+                }
+            }
+        }
+        false
+    }
+
+    /// Returns the set of vertices visited by a breadth first search from start, including start
+    /// itself, expanding each frontier in parallel. This yields the same vertices as bfs.
+    /// Returns NoSuchVertex GraphError, if start is not in graph
+    #[cfg(feature = "rayon")]
+    pub fn par_reachable_set(&self, start: &T) -> Result<HashSet<T>, GraphError> where T: Send + Sync {
+        if !self.vertices.contains(start) {
+            return Err(GraphError::NoSuchVertex);
+        }
+
+        // Only the adjacency table is shared between threads, so U does not have to be Sync
+        let outbound_table = &self.outbound_table;
+        let mut visited: HashSet<T> = HashSet::new();
+        visited.insert(*start);
+        let mut frontier: Vec<T> = vec![*start];
+        while !frontier.is_empty() {
+            let candidates: Vec<T> = frontier.par_iter()
+                .flat_map_iter(|vertex| outbound_table.get(vertex).into_iter().flatten().copied())
+                .collect();
+            frontier = candidates.into_iter()
+                .filter(|vertex| visited.insert(*vertex))
+                .collect();
+        }
+        Ok(visited)
+    }
+
+    /// Returns a random spanning tree of the undirected projection of the graph, found by a depth
+    /// first search which visits neighbours in random order. The tree contains all vertices,
+    /// labels and values, and its edges keep the direction and weight they have in the graph.
+    /// Returns None if the graph is not weakly connected
+    pub fn random_spanning_tree(&self, rng: &mut impl Rng) -> Option<Graph<T, U>> where U: Clone {
+        let mut tree: Graph<T, U> = Graph::new();
+        let vertices: Vec<T> = self.vertices.iter().copied().collect();
+        let root = match vertices.choose(rng) {
+            Some(root) => *root,
+            None => return Some(tree),
+        };
+        for vertex in &vertices {
+            match self.vertex_labels.get(vertex) {
+                Some(label) => {tree.add_vertex_with_label(*vertex, label);},
+                None => {tree.add_vertex(*vertex);},
+            }
+        }
+        tree.vertex_values.clone_from(&self.vertex_values);
+
+        let mut visited: HashSet<T> = HashSet::new();
+        visited.insert(root);
+        let mut stack: Vec<(T, T)> = Vec::new();
+        let mut neighbours: Vec<T> = self.undirected_neighbours(&root).into_iter().collect();
+        neighbours.shuffle(rng);
+        stack.extend(neighbours.into_iter().map(|neighbour| (root, neighbour)));
+
+        while let Some((parent, current)) = stack.pop() {
+            if !visited.insert(current) {
+                continue;
+            }
+            let edge = if self.edges.contains(&(parent, current)) { (parent, current) } else { (current, parent) };
+            let _ = tree.add_edge(edge.0, edge.1);
+            if let Some(weight) = self.edge_weights.get(&edge) {
+                tree.edge_weights.insert(edge, *weight);
+            }
+
+            let mut neighbours: Vec<T> = self.undirected_neighbours(&current).into_iter()
+                .filter(|neighbour| !visited.contains(neighbour))
+                .collect();
+            neighbours.shuffle(rng);
+            stack.extend(neighbours.into_iter().map(|neighbour| (current, neighbour)));
+        }
+
+        if visited.len() < self.vertices.len() {
+            return None;
+        }
+        Some(tree)
+    }
+
+    /// Returns the weakly connected components of the graph, i.e. the connected components when
+    /// ignoring edge directions. Every vertex is in exactly one component and isolated vertices
+    /// form their own component. The order of the components is unspecified
+    pub fn weakly_connected_components(&self) -> Vec<Vec<T>> {
+        let mut visited: HashSet<T> = HashSet::new();
+        let mut components: Vec<Vec<T>> = Vec::new();
+
+        for start in &self.vertices {
+            if !visited.insert(*start) {
+                continue;
+            }
+            let mut component = vec![*start];
+            let mut stack = vec![*start];
+            while let Some(current) = stack.pop() {
+                for neighbour in self.undirected_neighbours(&current) {
+                    if visited.insert(neighbour) {
+                        component.push(neighbour);
+                        stack.push(neighbour);
+                    }
+                }
+            }
+            components.push(component);
+        }
+        components
+    }
+
+    /// Returns a new graph with the same vertices, labels and values where the direction of every
+    /// edge is reversed. Weights are carried over to the reversed edges
+    pub fn transpose(&self) -> Graph<T, U> where U: Clone {
+        let mut transposed: Graph<T, U> = Graph::new();
+        for vertex in &self.vertices {
+            transposed.add_vertex(*vertex);
+        }
+        transposed.vertex_labels.clone_from(&self.vertex_labels);
+        transposed.vertex_values.clone_from(&self.vertex_values);
+        for (outbound, incoming) in &self.edges {
+            let _ = transposed.add_edge(*incoming, *outbound);
+        }
+        for ((outbound, incoming), weight) in &self.edge_weights {
+            transposed.edge_weights.insert((*incoming, *outbound), *weight);
+        }
+        transposed
+    }
+
+    /// Returns the outbound neighbours of the given vertex which are not in exclude.
+    /// Returns NoSuchVertex GraphError, if vertex is not in graph
+    pub fn out_neighbours_excluding(&self, vertex: &T, exclude: &HashSet<T>) -> Result<Vec<T>, GraphError> {
+        if !self.vertices.contains(vertex) {
+            return Err(GraphError::NoSuchVertex);
+        }
+        Ok(self.out_neighbours(vertex)
+            .filter(|neighbour| !exclude.contains(neighbour))
+            .copied()
+            .collect())
+    }
+
+    /// Returns the Wiener index of the graph, that is the sum of the directed distances over all
+    /// ordered pairs of distinct vertices. Since edges are directed, the pairs (a, b) and (b, a)
+    /// are both counted and may have different distances.
+    /// Returns None if some vertex cannot reach all others
+    pub fn wiener_index(&self) -> Option<usize> {
+        let mut index = 0;
+        for vertex in &self.vertices {
+            let distances = self.bfs_distances(vertex);
+            if distances.len() < self.vertices.len() {
+                return None;
+            }
+            index += distances.values().sum::<usize>();
+        }
+        Some(index)
+    }
+
+    /// Returns the graph in the Graphviz DOT format. Vertices are identified by their Debug form
+    /// and use their label as node label if present. Quotes in identifiers and labels are escaped
+    pub fn to_dot(&self) -> String where T: Debug {
+        let escape = |text: &str| text.replace('\\', "\\\\").replace('"', "\\\"");
+
+        let mut dot = String::from("digraph {\n");
+        for vertex in &self.vertices {
+            let id = escape(&format!("{:?}", vertex));
+            let label = match self.vertex_labels.get(vertex) {
+                Some(label) => escape(label),
+                None => id.clone(),
+            };
+            dot.push_str(&format!("    \"{}\" [label=\"{}\"];\n", id, label));
+        }
+        for (outbound, incoming) in &self.edges {
+            dot.push_str(&format!("    \"{}\" -> \"{}\";\n",
+                escape(&format!("{:?}", outbound)), escape(&format!("{:?}", incoming))));
+        }
+        dot.push('}');
+        dot
+    }
+
+    /// Returns a deterministic representation of the graph with one line per vertex in ascending
+    /// order, followed by its outbound neighbours in ascending order, e.g. "1: 2 3"
+    pub fn to_sorted_adjacency_string(&self) -> String where T: Ord + Display {
+        let mut vertices: Vec<&T> = self.vertices.iter().collect();
+        vertices.sort();
+
+        let mut lines: Vec<String> = Vec::with_capacity(vertices.len());
+        for vertex in vertices {
+            let mut neighbours: Vec<&T> = self.out_neighbours(vertex).collect();
+            neighbours.sort();
+            let mut line = format!("{}:", vertex);
+            for neighbour in neighbours {
+                line.push_str(&format!(" {}", neighbour));
+            }
+            lines.push(line);
+        }
+        lines.join("\n")
+    }
+
+    /// Returns a random vertex chosen with probability proportional to its degree in the
+    /// undirected projection of the graph. Returns None if no vertex has a neighbour
+    pub fn sample_vertex_by_degree(&self, rng: &mut impl Rng) -> Option<T> {
+        let vertices: Vec<T> = self.vertices.iter().copied().collect();
+        let degrees = vertices.iter().map(|vertex| self.undirected_neighbours(vertex).len());
+        let distribution = WeightedIndex::new(degrees).ok()?;
+        Some(vertices[distribution.sample(rng)])
+    }
+
+    /// Returns whether every vertex has at most k outbound edges
+    pub fn max_out_degree_at_most(&self, k: usize) -> bool {
+        self.outbound_table.values().all(|neighbours| neighbours.len() <= k)
+    }
+
+    /// Returns a vertex cover of the undirected projection of the graph, i.e. a set of vertices
+    /// containing at least one endpoint of every edge. Both endpoints of a greedily chosen maximal
+    /// matching are taken, so the cover is at most twice as large as a minimum one
+    pub fn approx_vertex_cover(&self) -> HashSet<T> {
+        let mut cover: HashSet<T> = HashSet::new();
+        for (outbound, incoming) in &self.edges {
+            if !cover.contains(outbound) && !cover.contains(incoming) {
+                cover.insert(*outbound);
+                cover.insert(*incoming);
+            }
+        }
+        cover
+    }
+
+    /// Returns a dominating set of the undirected projection of the graph, i.e. a set of vertices
+    /// such that every vertex is in the set or adjacent to a vertex in it. The set is built greedily
+    /// by repeatedly choosing the vertex which dominates the most vertices not yet dominated
+    pub fn greedy_dominating_set(&self) -> HashSet<T> {
+        let neighbourhoods: HashMap<T, HashSet<T>> = self.vertices.iter()
+            .map(|vertex| {
+                let mut closed = self.undirected_neighbours(vertex);
+                closed.insert(*vertex);
+                (*vertex, closed)
+            })
+            .collect();
+
+        let mut undominated: HashSet<T> = self.vertices.clone();
+        let mut dominating_set: HashSet<T> = HashSet::new();
+        while !undominated.is_empty() {
+            let best = neighbourhoods.iter()
+                .max_by_key(|(_, closed)| closed.intersection(&undominated).count())
+                .map(|(vertex, _)| *vertex)
+                .unwrap();
+            for dominated in &neighbourhoods[&best] {
+                undominated.remove(dominated);
+            }
+            dominating_set.insert(best);
+        }
+        dominating_set
+    }
+
+    /// Returns every edge with at least one endpoint in the given set of vertices. Each edge is
+    /// returned once and the order is unspecified
+    pub fn edges_incident_to_set(&self, vertices: &HashSet<T>) -> Vec<(T, T)> {
+        self.edges.iter()
+            .filter(|(outbound, incoming)| vertices.contains(outbound) || vertices.contains(incoming))
+            .copied()
+            .collect()
+    }
+
+    /// Adds all vertices, edges, labels, values and weights of other to this graph. Labels, values
+    /// and weights of other replace the existing ones on conflict
+    pub fn union(&mut self, other: &Graph<T, U>) where U: Clone {
+        for vertex in &other.vertices {
+            self.add_vertex(*vertex);
+        }
+        for (outbound, incoming) in &other.edges {
+            let _ = self.add_edge(*outbound, *incoming);
+        }
+        for (vertex, label) in &other.vertex_labels {
+            self.vertex_labels.insert(*vertex, label.clone());
+        }
+        for (vertex, value) in &other.vertex_values {
+            self.vertex_values.insert(*vertex, value.clone());
+        }
+        for (edge, weight) in &other.edge_weights {
+            self.edge_weights.insert(*edge, *weight);
+        }
+    }
+
+    /// Returns the global efficiency of the graph, i.e. the average of 1 / d(u, v) over all ordered
+    /// pairs of distinct vertices, where d is the directed distance and unreachable pairs
+    /// contribute 0. Graphs with fewer than two vertices have efficiency 0
+    pub fn global_efficiency(&self) -> f64 {
+        let n = self.vertices.len();
+        if n < 2 {
+            return 0.0;
+        }
+        let inverse_distance_sum: f64 = self.vertices.iter()
+            .flat_map(|vertex| self.bfs_distances(vertex).into_values())
+            .filter(|distance| *distance > 0)
+            .map(|distance| 1.0 / distance as f64)
+            .sum();
+        inverse_distance_sum / (n * (n - 1)) as f64
+    }
+
+    /// Returns the number of edges of the undirected projection of the graph, i.e. the number of
+    /// distinct unordered pairs of endpoints. Mutual edges and self-loops count once
+    pub fn undirected_edge_count(&self) -> usize {
+        let mutual = self.edges.iter()
+            .filter(|(outbound, incoming)| outbound != incoming && self.edges.contains(&(*incoming, *outbound)))
+            .count();
+        self.edges.len() - mutual / 2
+    }
+
+    /// Returns a new graph whose edges are the images of the edges of this graph under f. The
+    /// endpoints of the mapped edges are added as vertices, so vertices without edges are not
+    /// carried over. Edges which are mapped to the same edge collapse into one. Labels, values and
+    /// weights are dropped
+    pub fn map_edges<S: Eq + PartialEq + Hash + Copy, F: Fn(&(T, T)) -> (S, S)>(&self, f: F) -> Graph<S> {
+        let mut mapped: Graph<S> = Graph::new();
+        for edge in &self.edges {
+            let (outbound, incoming) = f(edge);
+            mapped.add_vertex(outbound);
+            mapped.add_vertex(incoming);
+            let _ = mapped.add_edge(outbound, incoming);
+        }
+        mapped
+    }
+
+    /// Returns an approximate center of the undirected projection of the graph using a double
+    /// breadth first search: starting from an arbitrary vertex the farthest vertex a is found,
+    /// then the farthest vertex b from a, and the midpoint of a shortest path between a and b is
+    /// returned. Only the component of the arbitrary vertex is considered. Returns None if the
+    /// graph is empty
+    pub fn approximate_center(&self) -> Option<T> {
+        let farthest = |distances: &HashMap<T, usize>| {
+            distances.iter()
+                .max_by_key(|(_, distance)| **distance)
+                .map(|(vertex, distance)| (*vertex, *distance))
+        };
+
+        let arbitrary = self.vertices.iter().next()?;
+        let (a, _) = farthest(&self.undirected_bfs_distances(arbitrary))?;
+        let from_a = self.undirected_bfs_distances(&a);
+        let (b, diameter) = farthest(&from_a)?;
+        let from_b = self.undirected_bfs_distances(&b);
+
+        // The midpoint lies on a shortest path between a and b, halfway from a
+        let half = diameter / 2;
+        from_a.into_iter()
+            .find(|(vertex, distance)| *distance == half && from_b.get(vertex) == Some(&(diameter - half)))
+            .map(|(vertex, _)| vertex)
+    }
+
+    /// Returns the number of bridges of the undirected projection of the graph, i.e. edges whose
+    /// removal increases the number of weakly connected components. Mutual edges form a single
+    /// undirected edge. Uses the low-link values of an iterative depth first search
+    pub fn bridge_count(&self) -> usize {
+        let mut discovery: HashMap<T, usize> = HashMap::new();
+        let mut low: HashMap<T, usize> = HashMap::new();
+        let mut bridges = 0;
+
+        for root in &self.vertices {
+            if discovery.contains_key(root) {
+                continue;
+            }
+            let time = discovery.len();
+            discovery.insert(*root, time);
+            low.insert(*root, time);
+            // Every stack frame holds a vertex, its parent and its remaining neighbours
+            let mut stack: Vec<(T, Option<T>, Vec<T>)> =
+                vec![(*root, None, self.undirected_neighbours(root).into_iter().collect())];
+
+            while let Some((current, parent, remaining)) = stack.last_mut() {
+                let current = *current;
+                let parent = *parent;
+                match remaining.pop() {
+                    Some(neighbour) => {
+                        if Some(neighbour) == parent {
+                            continue;
+                        }
+                        match discovery.get(&neighbour) {
+                            Some(time) => {
+                                let time = *time;
+                                let current_low = low.get_mut(&current).unwrap();
+                                *current_low = (*current_low).min(time);
+                            },
+                            None => {
+                                let time = discovery.len();
+                                discovery.insert(neighbour, time);
+                                low.insert(neighbour, time);
+                                let neighbours = self.undirected_neighbours(&neighbour).into_iter().collect();
+                                stack.push((neighbour, Some(current), neighbours));
+                            },
+                        }
+                    },
+                    None => {
+                        stack.pop();
+                        if let Some(parent) = parent {
+                            let current_low = low[&current];
+                            if current_low > discovery[&parent] {
+                                bridges += 1;
+                            }
+                            let parent_low = low.get_mut(&parent).unwrap();
+                            *parent_low = (*parent_low).min(current_low);
+                        }
+                    },
+                }
+            }
+        }
+        bridges
+    }
+
+    /// Collapses the given vertices into the single vertex into, which may be one of them or a new
+    /// vertex. Edges between a merged and an outside vertex are rewired to into, while edges
+    /// between merged vertices are dropped, so into has no self-loop afterwards. If several edges
+    /// collapse into one, the smallest weight is kept where edges without weight count as 1.
+    /// Labels and values of the merged vertices other than into are removed.
+    /// Returns NoSuchVertex GraphError, if one of the vertices is not in graph
+    pub fn merge_set(&mut self, vertices: &HashSet<T>, into: T) -> Result<(), GraphError> {
+        if !vertices.iter().all(|vertex| self.vertices.contains(vertex)) {
+            return Err(GraphError::NoSuchVertex);
+        }
+
+        let merged = |vertex: &T| *vertex == into || vertices.contains(vertex);
+        let mut rewired: HashMap<(T, T), Option<u64>> = HashMap::new();
+        for (outbound, incoming) in &self.edges {
+            if merged(outbound) == merged(incoming) {
+                continue;
+            }
+            let edge = (
+                if merged(outbound) { into } else { *outbound },
+                if merged(incoming) { into } else { *incoming },
+            );
+            let weight = self.edge_weights.get(&(*outbound, *incoming)).copied();
+            rewired.entry(edge)
+                .and_modify(|known| if known.is_some() || weight.is_some() {
+                    *known = Some(known.unwrap_or(1).min(weight.unwrap_or(1)));
+                })
+                .or_insert(weight);
+        }
+
+        for vertex in vertices {
+            if *vertex != into {
+                self.remove_vertex(vertex)?;
+            }
+        }
+        self.add_vertex(into);
+        self.detach_edge(&into, &into);
+        for ((outbound, incoming), weight) in rewired {
+            let _ = self.add_edge(outbound, incoming);
+            match weight {
+                Some(weight) => {self.edge_weights.insert((outbound, incoming), weight);},
+                None => {self.edge_weights.remove(&(outbound, incoming));},
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the density of the graph, i.e. the number of edges divided by the number of
+    /// possible edges V * (V - 1) of a directed graph. Self-loops are not counted and graphs with
+    /// at most one vertex have density 0
+    pub fn density(&self) -> f64 {
+        let n = self.vertices.len();
+        if n <= 1 {
+            return 0.0;
+        }
+        let edges = self.edges.iter().filter(|(outbound, incoming)| outbound != incoming).count();
+        edges as f64 / (n * (n - 1)) as f64
+    }
+
+    /// Returns every vertex which has an edge to itself. Each vertex is returned once and the
+    /// order is unspecified
+    pub fn self_loops(&self) -> Vec<T> {
+        self.edges.iter()
+            .filter(|(outbound, incoming)| outbound == incoming)
+            .map(|(vertex, _)| *vertex)
+            .collect()
+    }
+
+    /// Returns a fundamental cycle basis of the undirected projection of the graph. A breadth
+    /// first spanning forest is built and every edge not in it closes one cycle with the tree
+    /// path between its endpoints. Each cycle is returned as sequence of its distinct vertices.
+    /// Mutual edges form a single undirected edge and self-loops are ignored
+    pub fn cycle_basis(&self) -> Vec<Vec<T>> {
+        let mut parents: HashMap<T, T> = HashMap::new();
+        let mut depths: HashMap<T, usize> = HashMap::new();
+        for root in &self.vertices {
+            if depths.contains_key(root) {
+                continue;
+            }
+            depths.insert(*root, 0);
+            let mut queue: VecDeque<T> = VecDeque::from([*root]);
+            while let Some(current) = queue.pop_front() {
+                let depth = depths[&current] + 1;
+                for neighbour in self.undirected_neighbours(&current) {
+                    if let Entry::Vacant(entry) = depths.entry(neighbour) {
+                        entry.insert(depth);
+                        parents.insert(neighbour, current);
+                        queue.push_back(neighbour);
+                    }
+                }
+            }
+        }
+
+        let mut seen: HashSet<(T, T)> = HashSet::new();
+        let mut basis: Vec<Vec<T>> = Vec::new();
+        for vertex in &self.vertices {
+            for neighbour in self.undirected_neighbours(vertex) {
+                let is_tree_edge = parents.get(vertex) == Some(&neighbour) || parents.get(&neighbour) == Some(vertex);
+                if is_tree_edge || seen.contains(&(neighbour, *vertex)) {
+                    continue;
+                }
+                seen.insert((*vertex, neighbour));
+
+                // Walk up from both endpoints until the paths meet at their lowest common ancestor
+                let (mut a, mut b) = (*vertex, neighbour);
+                let mut from_a = vec![a];
+                let mut from_b = vec![b];
+                while a != b {
+                    if depths[&a] >= depths[&b] {
+                        a = parents[&a];
+                        from_a.push(a);
+                    } else {
+                        b = parents[&b];
+                        from_b.push(b);
+                    }
+                }
+                from_b.pop();
+                from_a.extend(from_b.into_iter().rev());
+                basis.push(from_a);
+            }
+        }
+        basis
+    }
+}
+
+
+impl<T: Eq + PartialEq + Hash + Copy, U> Default for Graph<T, U> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+
+/// Graphs are equal if they have the same vertices, edges, labels, weights and values. The
+/// adjacency tables are derived from the edges, so the order in which edges were added does not matter
+impl<T: Eq + PartialEq + Hash + Copy, U: PartialEq> PartialEq for Graph<T, U> {
+    fn eq(&self, other: &Self) -> bool {
+        self.vertices == other.vertices
+            && self.edges == other.edges
+            && self.vertex_labels == other.vertex_labels
+            && self.edge_weights == other.edge_weights
+            && self.vertex_values == other.vertex_values
+    }
+}
+
+
+#[cfg(feature = "serde")]
+#[derive(Serialize)]
+/// Borrowed serialized form of a graph. The adjacency tables are not serialized since they can be
+/// rebuilt from the edges
+struct SerializeGraph<'a, T, U> {
+    vertices: Vec<&'a T>,
+    edges: Vec<&'a (T, T)>,
+    vertex_labels: Vec<(&'a T, &'a String)>,
+    vertex_values: Vec<(&'a T, &'a U)>,
+    edge_weights: Vec<(&'a (T, T), &'a u64)>,
+}
+
+
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+/// Owned serialized form of a graph, see SerializeGraph
+struct DeserializeGraph<T, U> {
+    vertices: Vec<T>,
+    edges: Vec<(T, T)>,
+    vertex_labels: Vec<(T, String)>,
+    vertex_values: Vec<(T, U)>,
+    edge_weights: Vec<((T, T), u64)>,
+}
+
+
+#[cfg(feature = "serde")]
+impl<T: Eq + PartialEq + Hash + Copy + Serialize, U: Serialize> Serialize for Graph<T, U> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SerializeGraph {
+            vertices: self.vertices.iter().collect(),
+            edges: self.edges.iter().collect(),
+            vertex_labels: self.vertex_labels.iter().collect(),
+            vertex_values: self.vertex_values.iter().collect(),
+            edge_weights: self.edge_weights.iter().collect(),
+        }.serialize(serializer)
+    }
+}
+
+
+#[cfg(feature = "serde")]
+impl<'de, T: Eq + PartialEq + Hash + Copy + Deserialize<'de>, U: Deserialize<'de>> Deserialize<'de> for Graph<T, U> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = DeserializeGraph::<T, U>::deserialize(deserializer)?;
+        let invalid = |error: GraphError| D::Error::custom(format!("invalid graph: {:?}", error));
+
+        // Rebuild the graph through the regular methods so the adjacency tables are consistent
+        let mut graph: Graph<T, U> = Graph::new();
+        for vertex in data.vertices {
+            graph.add_vertex(vertex);
+        }
+        for (outbound, incoming) in data.edges {
+            graph.add_edge(outbound, incoming).map_err(invalid)?;
+        }
+        for (vertex, label) in data.vertex_labels {
+            graph.set_label(&vertex, &label).map_err(invalid)?;
+        }
+        for (vertex, value) in data.vertex_values {
+            graph.set_value(&vertex, value).map_err(invalid)?;
+        }
+        for ((outbound, incoming), weight) in data.edge_weights {
+            if !graph.is_edge_in_graph(outbound, incoming) {
+                return Err(invalid(GraphError::NoSuchEdge));
+            }
+            graph.edge_weights.insert((outbound, incoming), weight);
+        }
+        Ok(graph)
+    }
+}
+
+
+impl<T: Eq + PartialEq + Hash + Copy, U> Graph<T, U> {
+    /// Returns the set of neighbours of a vertex when ignoring edge directions, excluding the
+    /// vertex itself
+    fn undirected_neighbours(&self, vertex: &T) -> HashSet<T> {
+        self.in_neighbours(vertex)
+            .chain(self.out_neighbours(vertex))
+            .filter(|neighbour| *neighbour != vertex)
+            .copied()
+            .collect()
+    }
+
+    /// Removes an edge together with its weight and its entries in the adjacency tables.
+    /// Returns whether the edge was in the graph
+    fn detach_edge(&mut self, outbound: &T, incoming: &T) -> bool {
+        if !self.edges.remove(&(*outbound, *incoming)) {
+            return false;
+        }
+        self.edge_weights.remove(&(*outbound, *incoming));
+        self.purge_adjacency(outbound, incoming);
+        true
+    }
+
+    /// Removes every occurrence of incoming from the outbound table of outbound and of outbound
+    /// from the inbound table of incoming, dropping lists which become empty
+    fn purge_adjacency(&mut self, outbound: &T, incoming: &T) {
+        self.components = OnceLock::new();
+
+        if let Some(outbounds) = self.outbound_table.get_mut(outbound) {
+            outbounds.retain(|neighbour| neighbour != incoming);
+            if outbounds.is_empty() {
+                self.outbound_table.remove(outbound);
+            }
+        }
+        if let Some(inbounds) = self.inbound_table.get_mut(incoming) {
+            inbounds.retain(|neighbour| neighbour != outbound);
+            if inbounds.is_empty() {
+                self.inbound_table.remove(incoming);
+            }
+        }
+    }
+
+    /// Returns the directed distance from start to every vertex reachable from it, including
+    /// start itself with distance 0
+    fn bfs_distances(&self, start: &T) -> HashMap<T, usize> {
+        let mut distances: HashMap<T, usize> = HashMap::new();
+        let mut queue: VecDeque<T> = VecDeque::new();
+        distances.insert(*start, 0);
+        queue.push_back(*start);
+
+        while let Some(current) = queue.pop_front() {
+            let distance = distances[&current];
+            for neighbour in self.out_neighbours(&current) {
+                if !distances.contains_key(neighbour) {
+                    distances.insert(*neighbour, distance + 1);
+                    queue.push_back(*neighbour);
+                }
+            }
+        }
+        distances
+    }
+
+    /// Returns the largest directed distance from vertex to any other vertex or None if vertex
+    /// cannot reach all vertices of the graph
+    fn eccentricity_of(&self, vertex: &T) -> Option<usize> {
+        let distances = self.bfs_distances(vertex);
+        if distances.len() < self.vertices.len() {
+            return None;
+        }
+        distances.into_values().max()
+    }
+
+    /// Returns the set of vertices from which the given vertex can be reached via inbound edges,
+    /// including the vertex itself
+    fn reaching(&self, vertex: &T) -> HashSet<T> {
+        let mut visited: HashSet<T> = HashSet::new();
+        let mut stack = vec![*vertex];
+        visited.insert(*vertex);
+        while let Some(current) = stack.pop() {
+            for neighbour in self.in_neighbours(&current) {
+                if visited.insert(*neighbour) {
+                    stack.push(*neighbour);
+                }
+            }
+        }
+        visited
+    }
+
+    /// Returns the number of unordered pairs of the given distinct vertices which are connected
+    /// by an edge in either direction
+    fn connected_pairs(&self, vertices: &[T]) -> usize {
+        let mut pairs = 0;
+        for (i, a) in vertices.iter().enumerate() {
+            for b in &vertices[i + 1..] {
+                if self.edges.contains(&(*a, *b)) || self.edges.contains(&(*b, *a)) {
+                    pairs += 1;
+                }
+            }
+        }
+        pairs
+    }
+
+    /// Returns the distance from start to every vertex reachable from it when ignoring edge
+    /// directions, including start itself with distance 0
+    fn undirected_bfs_distances(&self, start: &T) -> HashMap<T, usize> {
+        let mut distances: HashMap<T, usize> = HashMap::new();
+        let mut queue: VecDeque<T> = VecDeque::new();
+        distances.insert(*start, 0);
+        queue.push_back(*start);
+
+        while let Some(current) = queue.pop_front() {
+            let distance = distances[&current];
+            for neighbour in self.undirected_neighbours(&current) {
+                if let Entry::Vacant(entry) = distances.entry(neighbour) {
+                    entry.insert(distance + 1);
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+        distances
+    }
+
+    /// Returns the number of inbound edges of every vertex
+    fn in_degrees(&self) -> HashMap<T, usize> {
+        let mut in_degrees: HashMap<T, usize> = self.vertices.iter()
+            .map(|vertex| (*vertex, 0))
+            .collect();
+        for (_, incoming) in &self.edges {
+            *in_degrees.entry(*incoming).or_insert(0) += 1;
+        }
+        in_degrees
+    }
+
+    /// Computes a maximum flow from source to sink with the given edge capacities by repeatedly
+    /// augmenting along shortest paths in the residual graph. Returns the flow value together with
+    /// the residual capacities of all vertex pairs which are connected by an edge. Residual
+    /// capacities and the flow are computed as u128, which cannot overflow for u64 capacities, and
+    /// the returned flow saturates at u64::MAX
+    fn edmonds_karp<F: Fn(&(T, T)) -> u64>(&self, source: &T, sink: &T, capacity: F) -> (u64, HashMap<(T, T), u128>) {
+        let mut residual: HashMap<(T, T), u128> = HashMap::new();
+        for (outbound, incoming) in &self.edges {
+            *residual.entry((*outbound, *incoming)).or_insert(0) += u128::from(capacity(&(*outbound, *incoming)));
+            residual.entry((*incoming, *outbound)).or_insert(0);
+        }
+        if source == sink {
+            return (0, residual);
+        }
+
+        let mut flow: u128 = 0;
+        loop {
+            // Breadth first search for a shortest augmenting path
+            let mut predecessors: HashMap<T, T> = HashMap::new();
+            let mut queue: VecDeque<T> = VecDeque::new();
+            queue.push_back(*source);
+            while let Some(current) = queue.pop_front() {
+                if current == *sink {
+                    break;
+                }
+                for neighbour in self.undirected_neighbours(&current) {
+                    if neighbour != *source
+                        && !predecessors.contains_key(&neighbour)
+                        && residual[&(current, neighbour)] > 0 {
+                        predecessors.insert(neighbour, current);
+                        queue.push_back(neighbour);
+                    }
+                }
+            }
+            if !predecessors.contains_key(sink) {
+                return (u64::try_from(flow).unwrap_or(u64::MAX), residual);
+            }
+
+            // Find the bottleneck and push flow along the path
+            let mut path: Vec<(T, T)> = Vec::new();
+            let mut step = *sink;
+            while step != *source {
+                path.push((predecessors[&step], step));
+                step = predecessors[&step];
+            }
+            let bottleneck = path.iter().map(|edge| residual[edge]).min().unwrap();
+            for (outbound, incoming) in path {
+                *residual.get_mut(&(outbound, incoming)).unwrap() -= bottleneck;
+                *residual.get_mut(&(incoming, outbound)).unwrap() += bottleneck;
+            }
+            flow += bottleneck;
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::panic::{RefUnwindSafe, UnwindSafe};
+
+    #[test]
+    fn creating_empty_graph() {
+        let g: Graph<u32> = Graph::new();
+        assert_eq!(g.edges, HashSet::new());
+        assert_eq!(g.vertex_labels, HashMap::new());
+        assert_eq!(g.vertex_values, HashMap::new());
+        assert_eq!(g.edge_weights, HashMap::new());
+        assert_eq!(g.inbound_table, HashMap::new());
+        assert_eq!(g.outbound_table, HashMap::new());
+    }
+
+    #[test]
+    fn adding_vertex_to_graph() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_vertex(32);
+        assert!(g.vertices.contains(&32));
+    }
+
+    #[test]
+    fn adding_multiple_vertices() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_vertex(32);
+        g.add_vertex(1);
+        g.add_vertex(2);
+        assert!(g.vertices.contains(&32));
+        assert!(g.vertices.contains(&1));
+        assert!(g.vertices.contains(&2));
+        assert!(!g.vertices.contains(&31));
+        assert_eq!(g.vertices.len(),3);
+        assert_eq!(g.number_of_vertices(),3);
+    }
+
+    #[test]
+    fn adding_edges() {
+        let mut g: Graph<u32> = Graph::new();
+        assert_eq!(g.number_of_edges(), 0);
+        g.add_vertex(2);
+        g.add_vertex(3);
+        let i = 3;
+        g.add_edge(2, i).unwrap();
+        g.add_edge(i, i).unwrap();
+    } 
+
+    #[test]
+    fn removing_vertices_and_simultaneously_removing_edges() {
+        let mut g: Graph<u32> = Graph::new();
+        assert_eq!(g.number_of_edges(), 0);
+        g.add_vertex(2);
+        g.add_vertex(3);
+        let i = 3;
+        g.add_edge(i, i).unwrap();
+        g.add_edge(2, i).unwrap();
+        g.remove_vertex(&i).unwrap();
+        assert!(!g.edges.contains(&(i,i)));
+    }
+
+    #[test]
+    fn add_vertices_with_label_and_get_label() {
+        let mut g: Graph<u32> = Graph::new();
+        assert_eq!(g.number_of_vertices(), 0);
+        g.add_vertex_with_label(1, "A");
+        g.add_vertex_with_label(2, "B");
+        g.add_vertex(3);
+        assert_eq!(g.number_of_vertices(), 3);
+
+        g.add_vertex_with_label(4,"");
+        assert_eq!(g.get_label(&1).unwrap(), "A");
+
+        if let Err(e) = g.remove_vertex(&2) {panic!("Error: {:?}", e);}
+
+        assert_eq!(g.get_label(&2), Option::None);
+        assert_eq!(g.get_label(&3), Option::None);
+        assert_eq!(g.get_label(&4).unwrap(), "");
+    } 
+
+    #[test]
+    fn neighbours_in() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_vertex(1);
+        g.add_vertex(2);
+        g.add_vertex(3);
+        
+        g.add_edge(1,2).unwrap();
+
+        assert_eq!(g.inbound_table.len(),1);
+        g.add_edge(1,3).unwrap();
+        let v: Vec<&u32> = g.out_neighbours(&1).collect();
+        assert_eq!(v.len(), 2);
+
+        assert_eq!(v[0], &2);
+    }
+
+    #[test]
+    fn is_vertex_in_graph_given_vertex_in_graph_return_true() {
+        let mut g: Graph<u32> = Graph::new();
+        assert_eq!(g.number_of_vertices(), 0);
+        g.add_vertex(1);
+        g.add_vertex(2);
+        g.add_vertex(3);
+        assert_eq!(g.number_of_vertices(), 3);
+
+        assert!(g.is_vertex_in_graph(&1));
+        assert!(g.is_vertex_in_graph(&2));
+        assert!(g.is_vertex_in_graph(&3));
+    }
+
+    #[test]
+    fn is_vertex_in_graph_given_vertex_not_in_graph_return_false() {
+        let mut g: Graph<u32> = Graph::new();
+        assert_eq!(g.number_of_vertices(), 0);
+        g.add_vertex(1);
+        g.add_vertex(2);
+        g.add_vertex(3);
+        assert_eq!(g.number_of_vertices(), 3);
+
+        assert!(!g.is_vertex_in_graph(&4));
+        assert!(!g.is_vertex_in_graph(&6));
+    } 
+
+    #[test]
+    fn is_edge_in_graph_given_edge_in_graph_return_true() {
+        let mut g: Graph<u32> = Graph::new();
+        assert_eq!(g.number_of_vertices(), 0);
+        g.add_vertex(1);
+        g.add_vertex(2);
+        g.add_vertex(3);
+        g.add_edge(2, 3).unwrap();
+        g.add_edge(2, 1).unwrap();
+        g.add_edge(1, 2).unwrap();
+        assert_eq!(g.number_of_vertices(), 3);
+
+        assert!(g.is_edge_in_graph(2, 3));
+        assert!(g.is_edge_in_graph(2, 1));
+        assert!(g.is_edge_in_graph(1, 2));
+    }
+
+    #[test]
+    fn is_edge_in_graph_given_edge_not_in_graph_return_false() {
+        let mut g: Graph<u32> = Graph::new();
+        assert_eq!(g.number_of_vertices(), 0);
+        g.add_vertex(1);
+        g.add_vertex(2);
+        g.add_vertex(3);
+        g.add_edge(2, 3).unwrap();
+        g.add_edge(2, 1).unwrap();
+        g.add_edge(1, 2).unwrap();
+        assert_eq!(g.number_of_vertices(), 3);
+
+        assert!(!g.is_edge_in_graph(1, 3));
+        assert!(!g.is_edge_in_graph(1, 1));
+        assert!(!g.is_edge_in_graph(3, 1));
+    } 
+
+    #[test]
+    fn set_label_given_vertex_in_graph_set_label() {
+        let mut g: Graph<u32> = Graph::new();
+        assert_eq!(g.number_of_vertices(), 0);
+        g.add_vertex_with_label(1, "A");
+        g.add_vertex_with_label(2, "B");
+        g.add_vertex(3);
+
+        g.set_label(&3 ,"C").unwrap();
+
+
+        assert_eq!(g.get_label(&3).unwrap() , "C");
+        assert_eq!(g.set_label(&4, ""), Err(GraphError::NoSuchVertex));
+    }
+
+    #[test]
+    fn random_subgraph_given_seed_keep_fraction_of_vertices_and_induced_edges() {
+        let mut g: Graph<u32> = Graph::new();
+        for i in 0..1000 {
+            g.add_vertex(i);
+        }
+        for i in 0..999 {
+            g.add_edge(i, i + 1).unwrap();
+        }
+
+        let mut rng = StdRng::seed_from_u64(42);
+        let sub = g.random_subgraph(0.3, &mut rng);
+
+        assert!(sub.number_of_vertices() > 200 && sub.number_of_vertices() < 400);
+        for (outbound, incoming) in &sub.edges {
+            assert!(sub.is_vertex_in_graph(outbound));
+            assert!(sub.is_vertex_in_graph(incoming));
+            assert!(g.is_edge_in_graph(*outbound, *incoming));
+        }
+    }
+
+    #[test]
+    fn core_numbers_given_clique_with_tail_return_layered_cores() {
+        let mut g: Graph<u32> = Graph::new();
+        for i in 1..=6 {
+            g.add_vertex(i);
+        }
+        // 4-clique on 1..=4
+        for a in 1..=4 {
+            for b in (a + 1)..=4 {
+                g.add_edge(a, b).unwrap();
+            }
+        }
+        // Tail 4 - 5 - 6
+        g.add_edge(4, 5).unwrap();
+        g.add_edge(6, 5).unwrap();
+        g.add_vertex(7);
+
+        let cores = g.core_numbers();
+        assert_eq!(cores.len(), 7);
+        for i in 1..=4 {
+            assert_eq!(cores[&i], 3);
+        }
+        assert_eq!(cores[&5], 1);
+        assert_eq!(cores[&6], 1);
+        assert_eq!(cores[&7], 0);
+    }
+
+    #[test]
+    fn edge_weight_mut_given_weighted_edge_update_weight() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_vertex(1);
+        g.add_vertex(2);
+        g.add_weighted_edge(1, 2, 5).unwrap();
+        g.add_edge(2, 1).unwrap();
+
+        *g.edge_weight_mut(&1, &2).unwrap() += 3;
+
+        assert_eq!(g.edge_weight(&1, &2), Some(&8));
+        assert_eq!(g.edge_weight_mut(&2, &1), None);
+        assert_eq!(g.edge_weight_mut(&1, &3), None);
+    }
+
+    #[test]
+    fn distinct_label_count_given_shared_labels_return_number_of_unique_labels() {
+        let mut g: Graph<u32> = Graph::new();
+        assert_eq!(g.distinct_label_count(), 0);
+        g.add_vertex_with_label(1, "win");
+        g.add_vertex_with_label(2, "loss");
+        g.add_vertex_with_label(3, "win");
+        g.add_vertex_with_label(4, "loss");
+        g.add_vertex(5);
+
+        assert_eq!(g.distinct_label_count(), 2);
+    }
+
+    #[test]
+    fn retain_edges_in_weight_band_remove_edges_outside_band() {
+        let mut g: Graph<u32> = Graph::new();
+        for i in 1..=4 {
+            g.add_vertex(i);
+        }
+        g.add_weighted_edge(1, 2, 1).unwrap();
+        g.add_weighted_edge(1, 3, 5).unwrap();
+        g.add_weighted_edge(2, 3, 10).unwrap();
+        g.add_weighted_edge(3, 4, 11).unwrap();
+        g.add_edge(4, 1).unwrap();
+
+        g.retain_edges_in_weight_band(5, 10);
+
+        assert_eq!(g.number_of_edges(), 2);
+        assert!(g.is_edge_in_graph(1, 3));
+        assert!(g.is_edge_in_graph(2, 3));
+        assert_eq!(g.out_neighbours(&1).collect::<Vec<&u32>>(), vec![&3]);
+        assert_eq!(g.out_neighbours(&3).count(), 0);
+        assert_eq!(g.in_neighbours(&1).count(), 0);
+        assert_eq!(g.edge_weight(&3, &4), None);
+    }
+
+    #[test]
+    fn degree_assortativity_given_star_graph_return_negative_value() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_vertex(0);
+        for i in 1..=5 {
+            g.add_vertex(i);
+            g.add_edge(0, i).unwrap();
+        }
+
+        assert!(g.degree_assortativity() < 0.0);
+        assert!((g.degree_assortativity() + 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn degree_assortativity_given_degenerate_graph_return_zero() {
+        let mut g: Graph<u32> = Graph::new();
+        assert_eq!(g.degree_assortativity(), 0.0);
+        g.add_vertex(1);
+        g.add_vertex(2);
+        g.add_edge(1, 2).unwrap();
+        assert_eq!(g.degree_assortativity(), 0.0);
+    }
+
+    #[test]
+    fn shortest_cycle_through_given_two_and_four_cycle_return_two_cycle() {
+        let mut g: Graph<u32> = Graph::new();
+        for i in 1..=5 {
+            g.add_vertex(i);
+        }
+        g.add_edge(1, 2).unwrap();
+        g.add_edge(2, 3).unwrap();
+        g.add_edge(3, 4).unwrap();
+        g.add_edge(4, 1).unwrap();
+        g.add_edge(1, 5).unwrap();
+        g.add_edge(5, 1).unwrap();
+
+        assert_eq!(g.shortest_cycle_through(&1), Ok(Some(vec![1, 5])));
+        assert_eq!(g.shortest_cycle_through(&6), Err(GraphError::NoSuchVertex));
+    }
+
+    #[test]
+    fn shortest_cycle_through_given_no_cycle_return_none() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_vertex(1);
+        g.add_vertex(2);
+        g.add_edge(1, 2).unwrap();
+        g.add_vertex(3);
+        g.add_edge(3, 3).unwrap();
+
+        assert_eq!(g.shortest_cycle_through(&1), Ok(None));
+        assert_eq!(g.shortest_cycle_through(&3), Ok(Some(vec![3])));
+    }
+
+    #[test]
+    fn edge_betweenness_given_two_clusters_return_bridge_as_maximum() {
+        let mut g: Graph<u32> = Graph::new();
+        for i in 1..=6 {
+            g.add_vertex(i);
+        }
+        // Two triangles 1-2-3 and 4-5-6 with edges in both directions, bridged by 3-4
+        for (a, b) in [(1, 2), (2, 3), (1, 3), (4, 5), (5, 6), (4, 6), (3, 4)] {
+            g.add_edge(a, b).unwrap();
+            g.add_edge(b, a).unwrap();
+        }
+
+        let betweenness = g.edge_betweenness();
+        assert_eq!(betweenness.len(), 14);
+        // Every pair of vertices from different clusters uses the bridge once in each direction
+        assert_eq!(betweenness[&(3, 4)], 9.0);
+        assert_eq!(betweenness[&(4, 3)], 9.0);
+        for (edge, value) in &betweenness {
+            if *edge != (3, 4) && *edge != (4, 3) {
+                assert!(*value < 9.0);
+            }
+        }
+    }
+
+    #[test]
+    fn girvan_newman_given_barbell_graph_return_both_cliques() {
+        let mut g: Graph<u32> = Graph::new();
+        for i in 1..=8 {
+            g.add_vertex(i);
+        }
+        // Two 4-cliques 1..=4 and 5..=8 connected by the single bridge 4-5
+        for offset in [0, 4] {
+            for a in 1..=4 {
+                for b in (a + 1)..=4 {
+                    g.add_edge(a + offset, b + offset).unwrap();
+                    g.add_edge(b + offset, a + offset).unwrap();
+                }
+            }
+        }
+        g.add_edge(4, 5).unwrap();
+        g.add_edge(5, 4).unwrap();
+
+        let communities = g.girvan_newman(2);
+        assert_eq!(communities.len(), 2);
+        let left: HashSet<u32> = (1..=4).collect();
+        let right: HashSet<u32> = (5..=8).collect();
+        assert!(communities.contains(&left));
+        assert!(communities.contains(&right));
+    }
+
+    #[test]
+    fn k_hop_neighbours_given_chain_return_only_vertices_within_k_hops() {
+        let mut g: Graph<u32> = Graph::new();
+        for i in 1..=5 {
+            g.add_vertex(i);
+        }
+        g.add_edge(1, 2).unwrap();
+        g.add_edge(2, 3).unwrap();
+        g.add_edge(3, 4).unwrap();
+        g.add_edge(4, 5).unwrap();
+        g.add_edge(1, 3).unwrap();
+
+        let neighbours = g.k_hop_neighbours(&1, 2).unwrap();
+        let expected: HashMap<u32, usize> = [(1, 0), (2, 1), (3, 1), (4, 2)].into_iter().collect();
+        assert_eq!(neighbours, expected);
+        assert_eq!(g.k_hop_neighbours(&1, 0).unwrap().len(), 1);
+        assert_eq!(g.k_hop_neighbours(&6, 2), Err(GraphError::NoSuchVertex));
+    }
+
+    #[test]
+    fn copy_into_given_presized_target_copy_contents() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_vertex_with_label(1, "A");
+        g.add_vertex(2);
+        g.add_vertex(3);
+        g.add_edge(1, 2).unwrap();
+        g.add_weighted_edge(2, 3, 4).unwrap();
+
+        let mut target: Graph<u32> = Graph::new();
+        for i in 10..100 {
+            target.add_vertex(i);
+        }
+        target.add_edge(10, 11).unwrap();
+        target.add_vertex_with_label(12, "B");
+
+        g.copy_into(&mut target);
+
+        assert_eq!(target.vertices, g.vertices);
+        assert_eq!(target.edges, g.edges);
+        assert_eq!(target.vertex_labels, g.vertex_labels);
+        assert_eq!(target.edge_weights, g.edge_weights);
+        assert_eq!(target.inbound_table, g.inbound_table);
+        assert_eq!(target.outbound_table, g.outbound_table);
+    }
+
+    #[test]
+    fn vertices_with_degree_at_least_given_star_graph_return_center() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_vertex(0);
+        for i in 1..=5 {
+            g.add_vertex(i);
+            g.add_edge(i, 0).unwrap();
+        }
+
+        let hubs: Vec<&u32> = g.vertices_with_degree_at_least(3).collect();
+        assert_eq!(hubs, vec![&0]);
+        assert_eq!(g.vertices_with_degree_at_least(1).count(), 6);
+    }
+
+    #[test]
+    fn radius_given_path_graph_return_eccentricity_of_center() {
+        let mut g: Graph<u32> = Graph::new();
+        assert_eq!(g.radius(), None);
+        for i in 1..=5 {
+            g.add_vertex(i);
+        }
+        for i in 1..5 {
+            g.add_edge(i, i + 1).unwrap();
+            g.add_edge(i + 1, i).unwrap();
+        }
+
+        assert_eq!(g.radius(), Some(2));
+        assert_eq!(g.eccentricity_of(&3), Some(2));
+
+        g.add_vertex(6);
+        assert_eq!(g.radius(), None);
+    }
+
+    #[test]
+    fn incidence_matrix_given_small_graph_return_expected_matrix() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_vertex(1);
+        g.add_vertex(2);
+        g.add_vertex(3);
+        g.add_edge(1, 2).unwrap();
+        g.add_edge(2, 3).unwrap();
+        g.add_edge(3, 1).unwrap();
+        g.add_edge(3, 3).unwrap();
+
+        let matrix = g.incidence_matrix(&[1, 2, 3], &[(1, 2), (2, 3), (3, 1), (3, 3)]);
+        assert_eq!(matrix, vec![
+            vec![-1, 0, 1, 0],
+            vec![1, -1, 0, 0],
+            vec![0, 1, -1, 0],
+        ]);
+    }
+
+    #[test]
+    fn count_edges_where_given_upward_predicate_count_matching_edges() {
+        let mut g: Graph<u32> = Graph::new();
+        for i in 1..=4 {
+            g.add_vertex(i);
+        }
+        g.add_edge(1, 2).unwrap();
+        g.add_edge(2, 4).unwrap();
+        g.add_edge(4, 3).unwrap();
+        g.add_edge(3, 3).unwrap();
+
+        assert_eq!(g.count_edges_where(|outbound, incoming| outbound < incoming), 2);
+        assert_eq!(g.count_edges_where(|outbound, incoming| outbound == incoming), 1);
+    }
+
+    #[test]
+    fn adamic_adar_given_shared_low_degree_neighbour_score_higher() {
+        let mut g: Graph<u32> = Graph::new();
+        for i in 1..=10 {
+            g.add_vertex(i);
+        }
+        // 1 and 2 share the neighbour 5 which has degree 2
+        g.add_edge(1, 5).unwrap();
+        g.add_edge(2, 5).unwrap();
+        // 3 and 4 share the neighbour 6 which has degree 6
+        for i in [3, 4, 7, 8, 9, 10] {
+            g.add_edge(i, 6).unwrap();
+        }
+
+        let low_degree_score = g.adamic_adar(&1, &2).unwrap();
+        let high_degree_score = g.adamic_adar(&3, &4).unwrap();
+        assert!(low_degree_score > high_degree_score);
+        assert!(high_degree_score > 0.0);
+        assert_eq!(g.adamic_adar(&1, &3).unwrap(), 0.0);
+        assert_eq!(g.adamic_adar(&1, &11), Err(GraphError::NoSuchVertex));
+    }
+
+    #[test]
+    fn component_count_given_interleaved_changes_stay_correct() {
+        let mut g: Graph<u32> = Graph::new();
+        assert_eq!(g.component_count(), 0);
+        g.add_vertex(1);
+        g.add_vertex(2);
+        g.add_vertex_with_label(3, "C");
+        assert_eq!(g.component_count(), 3);
+
+        g.add_edge(1, 2).unwrap();
+        assert_eq!(g.component_count(), 2);
+        g.add_edge(2, 1).unwrap();
+        assert_eq!(g.component_count(), 2);
+        g.add_edge(3, 2).unwrap();
+        assert_eq!(g.component_count(), 1);
+
+        g.add_vertex(4);
+        assert_eq!(g.component_count(), 2);
+        g.remove_vertex(&2).unwrap();
+        assert_eq!(g.component_count(), 3);
+
+        g.add_edge(4, 1).unwrap();
+        assert_eq!(g.component_count(), 2);
+        assert_eq!(g.component_count(), g.weakly_connected_components().len());
+    }
+
+    #[test]
+    fn max_out_degree_vertex_given_star_like_graph_return_hub() {
+        let mut g: Graph<u32> = Graph::new();
+        assert_eq!(g.max_out_degree_vertex(), None);
+        for i in 0..=5 {
+            g.add_vertex(i);
+        }
+        for i in 1..=4 {
+            g.add_edge(0, i).unwrap();
+        }
+        g.add_edge(1, 5).unwrap();
+        g.add_edge(5, 1).unwrap();
+
+        assert_eq!(g.max_out_degree_vertex(), Some((0, 4)));
+    }
+
+    #[test]
+    fn bfs_ordered_given_small_graph_return_exact_sequence() {
+        let mut g: Graph<u32> = Graph::new();
+        for i in 1..=7 {
+            g.add_vertex(i);
+        }
+        g.add_edge(1, 4).unwrap();
+        g.add_edge(1, 3).unwrap();
+        g.add_edge(1, 2).unwrap();
+        g.add_edge(3, 6).unwrap();
+        g.add_edge(2, 7).unwrap();
+        g.add_edge(2, 5).unwrap();
+        g.add_edge(5, 1).unwrap();
+
+        assert_eq!(g.bfs_ordered(&1), Ok(vec![1, 2, 3, 4, 5, 7, 6]));
+        assert_eq!(g.bfs_ordered(&8), Err(GraphError::NoSuchVertex));
+    }
+
+    #[test]
+    fn greedy_feedback_edge_set_given_single_cycle_return_one_edge() {
+        let mut g: Graph<u32> = Graph::new();
+        for i in 1..=5 {
+            g.add_vertex(i);
+        }
+        g.add_edge(1, 2).unwrap();
+        g.add_edge(2, 3).unwrap();
+        g.add_edge(3, 1).unwrap();
+        g.add_edge(3, 4).unwrap();
+        g.add_edge(5, 4).unwrap();
+
+        let feedback = g.greedy_feedback_edge_set();
+        assert_eq!(feedback.len(), 1);
+
+        let (outbound, incoming) = feedback[0];
+        assert!(g.is_edge_in_graph(outbound, incoming));
+        g.detach_edge(&outbound, &incoming);
+        for i in 1..=5 {
+            assert_eq!(g.shortest_cycle_through(&i), Ok(None));
+        }
+    }
+
+    #[test]
+    fn reachability_bitsets_given_graph_match_breadth_first_search() {
+        let mut g: Graph<u32> = Graph::new();
+        for i in 0..70 {
+            g.add_vertex(i);
+        }
+        for i in 0..69 {
+            if i % 10 != 9 {
+                g.add_edge(i, i + 1).unwrap();
+            }
+        }
+        g.add_edge(25, 20).unwrap();
+        g.add_edge(5, 65).unwrap();
+
+        let (order, bitsets) = g.reachability_bitsets();
+        assert_eq!(order.len(), 70);
+        for (i, start) in order.iter().enumerate() {
+            let distances = g.bfs_distances(start);
+            for (j, goal) in order.iter().enumerate() {
+                let expected = if start == goal {
+                    g.shortest_cycle_through(start).unwrap().is_some()
+                } else {
+                    distances.contains_key(goal)
+                };
+                assert_eq!(bitsets[i][j / 64] & (1 << (j % 64)) != 0, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn weighted_out_edges_given_mixed_edges_yield_optional_weights() {
+        let mut g: Graph<u32> = Graph::new();
+        for i in 1..=4 {
+            g.add_vertex(i);
+        }
+        g.add_weighted_edge(1, 2, 7).unwrap();
+        g.add_edge(1, 3).unwrap();
+        g.add_weighted_edge(1, 4, 2).unwrap();
+
+        let mut edges: Vec<(u32, Option<&u64>)> = g.weighted_out_edges(&1).collect();
+        edges.sort();
+        assert_eq!(edges, vec![(2, Some(&7)), (3, None), (4, Some(&2))]);
+        assert_eq!(g.weighted_out_edges(&2).count(), 0);
+    }
+
+    #[test]
+    fn group_by_label_given_two_labels_return_groups() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_vertex_with_label(1, "win");
+        g.add_vertex_with_label(2, "loss");
+        g.add_vertex_with_label(3, "win");
+        g.add_vertex(4);
+
+        let mut groups = g.group_by_label();
+        assert_eq!(groups.len(), 2);
+        groups.get_mut("win").unwrap().sort();
+        assert_eq!(groups["win"], vec![1, 3]);
+        assert_eq!(groups["loss"], vec![2]);
+    }
+
+    #[test]
+    fn add_path_given_four_vertices_add_three_edges() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_path(&[1, 2, 3, 4]).unwrap();
+
+        assert_eq!(g.number_of_vertices(), 4);
+        assert_eq!(g.number_of_edges(), 3);
+        assert!(g.is_edge_in_graph(1, 2));
+        assert!(g.is_edge_in_graph(2, 3));
+        assert!(g.is_edge_in_graph(3, 4));
+    }
+
+    #[test]
+    fn add_cycle_given_three_vertices_add_closed_loop() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_cycle(&[1, 2, 3]).unwrap();
+
+        assert_eq!(g.number_of_vertices(), 3);
+        assert_eq!(g.number_of_edges(), 3);
+        assert!(g.is_edge_in_graph(1, 2));
+        assert!(g.is_edge_in_graph(2, 3));
+        assert!(g.is_edge_in_graph(3, 1));
+        assert_eq!(g.shortest_cycle_through(&1), Ok(Some(vec![1, 2, 3])));
+        assert!(g.has_cycle());
+    }
+
+    #[test]
+    fn neighbourhood_density_given_fully_connected_neighbours_return_one() {
+        let mut g: Graph<u32> = Graph::new();
+        for i in 0..=4 {
+            g.add_vertex(i);
+        }
+        g.add_edge(0, 1).unwrap();
+        g.add_edge(2, 0).unwrap();
+        g.add_edge(0, 3).unwrap();
+        g.add_edge(1, 2).unwrap();
+        g.add_edge(3, 2).unwrap();
+        g.add_edge(1, 3).unwrap();
+
+        assert_eq!(g.neighbourhood_density(&0), Ok(1.0));
+
+        g.add_edge(4, 0).unwrap();
+        assert_eq!(g.neighbourhood_density(&0), Ok(0.5));
+        assert_eq!(g.neighbourhood_density(&4), Ok(0.0));
+        assert_eq!(g.neighbourhood_density(&5), Err(GraphError::NoSuchVertex));
+    }
+
+    #[test]
+    fn connected_to_given_vertex_on_cycle_return_whole_cycle() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_cycle(&[1, 2, 3, 4]).unwrap();
+        g.add_vertex(5);
+        g.add_vertex(6);
+        g.add_edge(4, 5).unwrap();
+        g.add_edge(6, 1).unwrap();
+
+        let expected: HashSet<u32> = (1..=4).collect();
+        assert_eq!(g.connected_to(&2), Ok(expected));
+        assert_eq!(g.connected_to(&5), Ok([5].into_iter().collect()));
+        assert_eq!(g.connected_to(&7), Err(GraphError::NoSuchVertex));
+    }
+
+    #[test]
+    fn longest_simple_path_len_given_small_graph_return_known_length() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_path(&[1, 2, 3, 4, 5]).unwrap();
+        g.add_vertex(6);
+        g.add_edge(1, 6).unwrap();
+        g.add_edge(6, 5).unwrap();
+        g.add_edge(5, 1).unwrap();
+        g.add_edge(3, 1).unwrap();
+
+        assert_eq!(g.longest_simple_path_len(&1, 1000), Ok(4));
+        assert_eq!(g.longest_simple_path_len(&6, 1000), Ok(5));
+        assert_eq!(g.longest_simple_path_len(&1, 0), Ok(0));
+        assert_eq!(g.longest_simple_path_len(&7, 1000), Err(GraphError::NoSuchVertex));
+    }
+
+    #[test]
+    fn triangle_counts_given_fan_return_per_vertex_counts() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_path(&[1, 2, 3, 4]).unwrap();
+        g.add_vertex(0);
+        for i in 1..=4 {
+            g.add_edge(0, i).unwrap();
+        }
+        g.add_vertex(5);
+        g.add_edge(5, 4).unwrap();
+
+        let counts = g.triangle_counts();
+        let expected: HashMap<u32, usize> = [(0, 3), (1, 1), (2, 2), (3, 2), (4, 1), (5, 0)].into_iter().collect();
+        assert_eq!(counts, expected);
+    }
+
+    #[test]
+    fn multi_source_bfs_distances_given_two_sources_return_nearest_distances() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_path(&[1, 2, 3, 4, 5, 6]).unwrap();
+        g.add_vertex(7);
+        g.add_edge(7, 5).unwrap();
+        g.add_vertex(8);
+
+        let distances = g.multi_source_bfs_distances(&[1, 7]).unwrap();
+        let expected: HashMap<u32, usize> = [(1, 0), (2, 1), (3, 2), (4, 3), (5, 1), (6, 2), (7, 0)].into_iter().collect();
+        assert_eq!(distances, expected);
+        assert_eq!(g.multi_source_bfs_distances(&[1, 9]), Err(GraphError::NoSuchVertex));
+    }
+
+    #[test]
+    fn remove_incident_edges_given_connected_vertex_isolate_it() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_path(&[1, 2, 3]).unwrap();
+        g.add_edge(2, 2).unwrap();
+        g.add_edge(2, 1).unwrap();
+        g.add_edge(3, 1).unwrap();
+
+        assert_eq!(g.remove_incident_edges(&2), Ok(4));
+        assert!(g.is_vertex_in_graph(&2));
+        assert_eq!(g.out_neighbours(&2).count(), 0);
+        assert_eq!(g.in_neighbours(&2).count(), 0);
+        assert_eq!(g.number_of_edges(), 1);
+        assert_eq!(g.out_neighbours(&1).count(), 0);
+        assert_eq!(g.remove_incident_edges(&4), Err(GraphError::NoSuchVertex));
+    }
+
+    #[test]
+    fn is_subgraph_of_given_superset_return_true_and_extra_edge_return_false() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_path(&[1, 2, 3]).unwrap();
+
+        let mut superset: Graph<u32> = Graph::new();
+        superset.add_path(&[1, 2, 3, 4]).unwrap();
+        superset.add_edge(3, 1).unwrap();
+
+        assert!(g.is_subgraph_of(&superset));
+        assert!(!superset.is_subgraph_of(&g));
+
+        let mut extra_edge: Graph<u32> = Graph::new();
+        extra_edge.add_path(&[1, 2, 3]).unwrap();
+        extra_edge.add_edge(2, 1).unwrap();
+        assert!(!extra_edge.is_subgraph_of(&superset));
+    }
+
+    #[test]
+    fn component_diameters_given_two_components_return_both_diameters() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_path(&[1, 2, 3, 4]).unwrap();
+        g.add_vertex(5);
+        g.add_vertex(6);
+        g.add_vertex(7);
+        g.add_edge(6, 5).unwrap();
+        g.add_edge(6, 7).unwrap();
+        g.add_vertex(8);
+
+        let mut diameters = g.component_diameters();
+        diameters.sort();
+        assert_eq!(diameters, vec![0, 2, 3]);
+    }
+
+    #[test]
+    fn to_metis_given_small_graph_return_header_and_adjacency_lines() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_cycle(&[1, 2, 3]).unwrap();
+        g.add_vertex(4);
+        g.add_edge(4, 1).unwrap();
+        g.add_edge(1, 4).unwrap();
+        g.add_edge(4, 4).unwrap();
+
+        let metis = g.to_metis(&[1, 2, 3, 4]);
+        let lines: Vec<&str> = metis.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[0], "4 4");
+        assert_eq!(lines[1], "2 3 4");
+        assert_eq!(lines[2], "1 3");
+        assert_eq!(lines[4], "1");
+    }
+
+    #[test]
+    fn all_eccentricities_given_path_graph_return_endpoint_and_center_values() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_path(&[1, 2, 3, 4, 5]).unwrap();
+        g.add_path(&[5, 4, 3, 2, 1]).unwrap();
+
+        let eccentricities = g.all_eccentricities();
+        assert_eq!(eccentricities[&1], Some(4));
+        assert_eq!(eccentricities[&2], Some(3));
+        assert_eq!(eccentricities[&3], Some(2));
+        assert_eq!(eccentricities[&5], Some(4));
+
+        let mut directed: Graph<u32> = Graph::new();
+        directed.add_path(&[1, 2, 3]).unwrap();
+        let eccentricities = directed.all_eccentricities();
+        assert_eq!(eccentricities[&1], Some(2));
+        assert_eq!(eccentricities[&2], None);
+    }
+
+    #[test]
+    fn swap_labels_given_labelled_vertices_exchange_labels() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_vertex_with_label(1, "A");
+        g.add_vertex_with_label(2, "B");
+        g.add_vertex(3);
+
+        g.swap_labels(&1, &2).unwrap();
+        assert_eq!(g.get_label(&1).unwrap(), "B");
+        assert_eq!(g.get_label(&2).unwrap(), "A");
+
+        g.swap_labels(&1, &3).unwrap();
+        assert_eq!(g.get_label(&1), None);
+        assert_eq!(g.get_label(&3).unwrap(), "B");
+
+        assert_eq!(g.swap_labels(&1, &4), Err(GraphError::NoSuchVertex));
+    }
+
+    #[test]
+    fn descendant_count_given_branching_dag_return_number_of_descendants() {
+        let mut g: Graph<u32> = Graph::new();
+        for i in 1..=7 {
+            g.add_vertex(i);
+        }
+        g.add_edge(1, 2).unwrap();
+        g.add_edge(1, 3).unwrap();
+        g.add_edge(2, 4).unwrap();
+        g.add_edge(2, 5).unwrap();
+        g.add_edge(3, 5).unwrap();
+        g.add_edge(3, 6).unwrap();
+
+        assert_eq!(g.descendant_count(&1), Ok(5));
+        assert_eq!(g.descendant_count(&3), Ok(2));
+        assert_eq!(g.descendant_count(&7), Ok(0));
+        assert_eq!(g.descendant_count(&8), Err(GraphError::NoSuchVertex));
+    }
+
+    #[test]
+    fn ancestor_count_given_converging_dag_return_number_of_ancestors() {
+        let mut g: Graph<u32> = Graph::new();
+        for i in 1..=6 {
+            g.add_vertex(i);
+        }
+        g.add_edge(1, 3).unwrap();
+        g.add_edge(2, 3).unwrap();
+        g.add_edge(3, 5).unwrap();
+        g.add_edge(4, 5).unwrap();
+        g.add_edge(5, 6).unwrap();
+
+        assert_eq!(g.ancestor_count(&6), Ok(5));
+        assert_eq!(g.ancestor_count(&3), Ok(2));
+        assert_eq!(g.ancestor_count(&1), Ok(0));
+        assert_eq!(g.ancestor_count(&7), Err(GraphError::NoSuchVertex));
+    }
+
+    #[test]
+    fn lexicographic_topological_sort_given_dag_return_smallest_order() {
+        let mut g: Graph<u32> = Graph::new();
+        for i in 1..=6 {
+            g.add_vertex(i);
+        }
+        g.add_edge(5, 2).unwrap();
+        g.add_edge(4, 2).unwrap();
+        g.add_edge(2, 1).unwrap();
+        g.add_edge(3, 1).unwrap();
+        g.add_edge(6, 3).unwrap();
+
+        assert_eq!(g.lexicographic_topological_sort(), Ok(vec![4, 5, 2, 6, 3, 1]));
+
+        g.add_edge(1, 6).unwrap();
+        assert_eq!(g.lexicographic_topological_sort(), Err(GraphError::CycleDetected));
+    }
+
+    #[test]
+    fn contains_walk_given_sequence_revisiting_cycle_return_true() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_cycle(&[1, 2, 3]).unwrap();
+        g.add_vertex(4);
+        g.add_edge(3, 4).unwrap();
+
+        assert!(g.contains_walk(&[1, 2, 3, 1, 2, 3, 4]));
+        assert!(g.contains_walk(&[2]));
+        assert!(g.contains_walk(&[]));
+        assert!(!g.contains_walk(&[1, 3]));
+        assert!(!g.contains_walk(&[5]));
+    }
+
+    #[test]
+    fn remove_edge_given_edge_in_graph_return_ok() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_path(&[1, 2, 3]).unwrap();
+
+        assert_eq!(g.remove_edge(&1, &2), Ok(()));
+        assert!(!g.is_edge_in_graph(1, 2));
+        assert_eq!(g.number_of_edges(), 1);
+    }
+
+    #[test]
+    fn remove_edge_given_edge_not_in_graph_return_no_such_edge() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_path(&[1, 2, 3]).unwrap();
+
+        assert_eq!(g.remove_edge(&2, &1), Err(GraphError::NoSuchEdge));
+        assert_eq!(g.remove_edge(&1, &3), Err(GraphError::NoSuchEdge));
+        g.remove_edge(&1, &2).unwrap();
+        assert_eq!(g.remove_edge(&1, &2), Err(GraphError::NoSuchEdge));
+        assert_eq!(g.number_of_edges(), 1);
+    }
+
+    #[test]
+    fn edge_counts_by_label_given_labelled_graph_return_per_pair_counts() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_vertex_with_label(1, "A");
+        g.add_vertex_with_label(2, "A");
+        g.add_vertex_with_label(3, "B");
+        g.add_vertex(4);
+        g.add_edge(1, 2).unwrap();
+        g.add_edge(1, 3).unwrap();
+        g.add_edge(2, 3).unwrap();
+        g.add_edge(3, 4).unwrap();
+
+        let counts = g.edge_counts_by_label();
+        assert_eq!(counts.len(), 3);
+        assert_eq!(counts[&("A".to_owned(), "A".to_owned())], 1);
+        assert_eq!(counts[&("A".to_owned(), "B".to_owned())], 2);
+        assert_eq!(counts[&("B".to_owned(), "".to_owned())], 1);
+    }
+
+    #[test]
+    fn remove_edge_given_edge_in_graph_clean_up_neighbours() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_vertex(1);
+        g.add_vertex(2);
+        g.add_weighted_edge(1, 2, 3).unwrap();
+
+        g.remove_edge(&1, &2).unwrap();
+
+        assert_eq!(g.out_neighbours(&1).count(), 0);
+        assert_eq!(g.in_neighbours(&2).count(), 0);
+        assert!(!g.outbound_table.contains_key(&1));
+        assert!(!g.inbound_table.contains_key(&2));
+        assert_eq!(g.edge_weight(&1, &2), None);
+    }
+
+    #[test]
+    fn removing_vertex_clean_up_neighbours_of_other_vertices() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_path(&[1, 2, 3]).unwrap();
+        g.add_edge(2, 2).unwrap();
+
+        g.remove_vertex(&2).unwrap();
+
+        assert_eq!(g.number_of_edges(), 0);
+        assert_eq!(g.out_neighbours(&1).count(), 0);
+        assert_eq!(g.in_neighbours(&3).count(), 0);
+    }
+
+    #[test]
+    fn is_cut_vertex_given_bridge_vertex_return_true_and_leaf_return_false() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_cycle(&[1, 2, 3]).unwrap();
+        g.add_path(&[3, 4, 5]).unwrap();
+        g.add_vertex(6);
+
+        assert_eq!(g.is_cut_vertex(&3), Ok(true));
+        assert_eq!(g.is_cut_vertex(&4), Ok(true));
+        assert_eq!(g.is_cut_vertex(&5), Ok(false));
+        assert_eq!(g.is_cut_vertex(&1), Ok(false));
+        assert_eq!(g.is_cut_vertex(&6), Ok(false));
+        assert_eq!(g.is_cut_vertex(&7), Err(GraphError::NoSuchVertex));
+    }
+
+    #[test]
+    fn adding_same_edge_multiple_times_keep_single_neighbour_entry() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_vertex(1);
+        g.add_vertex(2);
+        g.add_edge(1, 2).unwrap();
+        g.add_edge(1, 2).unwrap();
+        g.add_edge(1, 2).unwrap();
+
+        assert_eq!(g.out_neighbours(&1).collect::<Vec<&u32>>(), vec![&2]);
+        assert_eq!(g.in_neighbours(&2).collect::<Vec<&u32>>(), vec![&1]);
+        assert_eq!(g.number_of_edges(), 1);
+    }
+
+    #[test]
+    fn cloning_graph_and_mutating_clone_leave_original_untouched() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_vertex_with_label(1, "A");
+        g.add_vertex_with_label(2, "B");
+        g.add_vertex(3);
+        g.add_edge(1, 2).unwrap();
+        g.add_weighted_edge(2, 3, 4).unwrap();
+
+        let mut clone = g.clone();
+        clone.set_label(&1, "C").unwrap();
+        clone.remove_edge(&1, &2).unwrap();
+        clone.add_edge(3, 1).unwrap();
+        clone.remove_vertex(&2).unwrap();
+
+        assert_eq!(g.number_of_vertices(), 3);
+        assert_eq!(g.number_of_edges(), 2);
+        assert_eq!(g.get_label(&1).unwrap(), "A");
+        assert!(g.is_edge_in_graph(1, 2));
+        assert!(!g.is_edge_in_graph(3, 1));
+        assert_eq!(g.edge_weight(&2, &3), Some(&4));
+        assert_eq!(g.out_neighbours(&1).collect::<Vec<&u32>>(), vec![&2]);
+        assert_eq!(g.in_neighbours(&1).count(), 0);
+        assert_eq!(g.component_count(), 1);
+    }
+
+    #[test]
+    fn max_flow_given_small_network_return_known_value() {
+        let mut g: Graph<u32> = Graph::new();
+        for i in 1..=6 {
+            g.add_vertex(i);
+        }
+        // Classic network with source 1, sink 6 and maximum flow 23
+        g.add_weighted_edge(1, 2, 16).unwrap();
+        g.add_weighted_edge(1, 3, 13).unwrap();
+        g.add_weighted_edge(2, 3, 10).unwrap();
+        g.add_weighted_edge(3, 2, 4).unwrap();
+        g.add_weighted_edge(2, 4, 12).unwrap();
+        g.add_weighted_edge(4, 3, 9).unwrap();
+        g.add_weighted_edge(3, 5, 14).unwrap();
+        g.add_weighted_edge(5, 4, 7).unwrap();
+        g.add_weighted_edge(4, 6, 20).unwrap();
+        g.add_weighted_edge(5, 6, 4).unwrap();
+
+        assert_eq!(g.max_flow(&1, &6), Ok(23));
+        assert_eq!(g.max_flow(&6, &1), Ok(0));
+        assert_eq!(g.max_flow(&1, &1), Ok(0));
+        assert_eq!(g.max_flow(&1, &7), Err(GraphError::NoSuchVertex));
+    }
+
+    #[test]
+    fn min_cut_given_bottleneck_return_bottleneck_edges() {
+        let mut g: Graph<u32> = Graph::new();
+        for i in 1..=6 {
+            g.add_vertex(i);
+        }
+        g.add_weighted_edge(1, 2, 10).unwrap();
+        g.add_weighted_edge(1, 3, 10).unwrap();
+        g.add_weighted_edge(2, 3, 5).unwrap();
+        g.add_weighted_edge(2, 4, 2).unwrap();
+        g.add_weighted_edge(3, 5, 3).unwrap();
+        g.add_weighted_edge(4, 6, 10).unwrap();
+        g.add_weighted_edge(5, 6, 10).unwrap();
+        g.add_weighted_edge(5, 4, 10).unwrap();
+
+        let (value, mut cut_edges) = g.min_cut(&1, &6).unwrap();
+        cut_edges.sort();
+        assert_eq!(value, 5);
+        assert_eq!(cut_edges, vec![(2, 4), (3, 5)]);
+        assert_eq!(g.min_cut(&0, &6), Err(GraphError::NoSuchVertex));
+    }
+
+    #[test]
+    fn graphs_built_in_different_order_are_equal() {
+        let mut a: Graph<u32> = Graph::new();
+        a.add_vertex_with_label(1, "A");
+        a.add_vertex(2);
+        a.add_vertex(3);
+        a.add_edge(1, 2).unwrap();
+        a.add_edge(1, 3).unwrap();
+        a.add_edge(3, 2).unwrap();
+
+        let mut b: Graph<u32> = Graph::new();
+        b.add_vertex(3);
+        b.add_vertex(2);
+        b.add_vertex_with_label(1, "A");
+        b.add_edge(3, 2).unwrap();
+        b.add_edge(1, 3).unwrap();
+        b.add_edge(1, 2).unwrap();
+
+        assert!(a == b);
+    }
+
+    #[test]
+    fn graphs_differing_in_single_label_are_not_equal() {
+        let mut a: Graph<u32> = Graph::new();
+        a.add_path(&[1, 2, 3]).unwrap();
+        a.set_label(&2, "B").unwrap();
+        let mut b = a.clone();
+        assert!(a == b);
+
+        b.set_label(&2, "C").unwrap();
+        assert!(a != b);
+    }
+
+    #[test]
+    fn degrees_given_self_loop_count_towards_both() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_path(&[1, 2, 3]).unwrap();
+        g.add_edge(2, 2).unwrap();
+        g.add_vertex(4);
+
+        assert_eq!(g.out_degree(&1), Some(1));
+        assert_eq!(g.in_degree(&1), Some(0));
+        assert_eq!(g.out_degree(&2), Some(2));
+        assert_eq!(g.in_degree(&2), Some(2));
+        assert_eq!(g.out_degree(&4), Some(0));
+        assert_eq!(g.in_degree(&4), Some(0));
+        assert_eq!(g.out_degree(&5), None);
+        assert_eq!(g.in_degree(&5), None);
+    }
+
+    #[test]
+    fn automorphism_count_given_cycles_return_number_of_symmetries() {
+        let mut directed: Graph<u32> = Graph::new();
+        directed.add_cycle(&[1, 2, 3, 4, 5]).unwrap();
+        assert_eq!(directed.automorphism_count(), 5);
+
+        let mut undirected: Graph<u32> = Graph::new();
+        undirected.add_cycle(&[1, 2, 3, 4]).unwrap();
+        undirected.add_cycle(&[4, 3, 2, 1]).unwrap();
+        assert_eq!(undirected.automorphism_count(), 8);
+
+        let mut empty: Graph<u32> = Graph::new();
+        assert_eq!(empty.automorphism_count(), 1);
+        empty.add_vertex(1);
+        empty.add_vertex(2);
+        empty.add_vertex(3);
+        assert_eq!(empty.automorphism_count(), 6);
+    }
+
+    #[test]
+    fn double_edge_swap_given_seed_preserve_degree_sequence() {
+        let mut g: Graph<u32> = Graph::new();
+        for i in 0..20 {
+            g.add_vertex(i);
+        }
+        for i in 0..20 {
+            g.add_edge(i, (i + 1) % 20).unwrap();
+            g.add_edge(i, (i + 3) % 20).unwrap();
+        }
+        g.add_edge(0, 10).unwrap();
+        let degrees: Vec<(Option<usize>, Option<usize>)> = (0..20)
+            .map(|i| (g.out_degree(&i), g.in_degree(&i)))
+            .collect();
+        let edge_count = g.number_of_edges();
+
+        let mut rng = StdRng::seed_from_u64(7);
+        let swaps = g.double_edge_swap(&mut rng, 100);
+
+        assert!(swaps > 0);
+        assert_eq!(g.number_of_edges(), edge_count);
+        for i in 0..20 {
+            assert_eq!((g.out_degree(&i), g.in_degree(&i)), degrees[i as usize]);
+            assert!(!g.is_edge_in_graph(i, i));
+        }
+    }
+
+    #[test]
+    fn bfs_given_dag_return_breadth_first_order() {
+        let mut g: Graph<u32> = Graph::new();
+        for i in 1..=6 {
+            g.add_vertex(i);
+        }
+        g.add_edge(1, 2).unwrap();
+        g.add_edge(1, 3).unwrap();
+        g.add_edge(2, 4).unwrap();
+        g.add_edge(3, 5).unwrap();
+        g.add_edge(4, 6).unwrap();
+        g.add_edge(5, 6).unwrap();
+
+        assert_eq!(g.bfs(&1), Ok(vec![1, 2, 3, 4, 5, 6]));
+        assert_eq!(g.bfs(&5), Ok(vec![5, 6]));
+        assert_eq!(g.bfs(&7), Err(GraphError::NoSuchVertex));
+    }
+
+    #[test]
+    fn bfs_given_cycle_visit_every_vertex_once() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_cycle(&[1, 2, 3, 4]).unwrap();
+        g.add_edge(1, 1).unwrap();
+        g.add_edge(1, 3).unwrap();
+
+        assert_eq!(g.bfs(&1), Ok(vec![1, 2, 3, 4]));
+        assert_eq!(g.bfs(&3), Ok(vec![3, 4, 1, 2]));
+    }
+
+    #[test]
+    fn dfs_given_branching_graph_differ_from_bfs() {
+        let mut g: Graph<u32> = Graph::new();
+        for i in 1..=6 {
+            g.add_vertex(i);
+        }
+        g.add_edge(1, 2).unwrap();
+        g.add_edge(1, 3).unwrap();
+        g.add_edge(2, 4).unwrap();
+        g.add_edge(2, 5).unwrap();
+        g.add_edge(3, 6).unwrap();
+        g.add_edge(6, 1).unwrap();
+
+        assert_eq!(g.dfs(&1), Ok(vec![1, 2, 4, 5, 3, 6]));
+        assert_eq!(g.bfs(&1), Ok(vec![1, 2, 3, 4, 5, 6]));
+        assert_eq!(g.dfs(&6), Ok(vec![6, 1, 2, 4, 5, 3]));
+        assert_eq!(g.dfs(&7), Err(GraphError::NoSuchVertex));
+    }
+
+    #[test]
+    fn spanning_forest_given_two_components_return_acyclic_forest() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_cycle(&[1, 2, 3, 4]).unwrap();
+        g.add_edge(1, 3).unwrap();
+        g.add_edge(3, 1).unwrap();
+        g.add_cycle(&[5, 6, 7]).unwrap();
+        g.add_vertex_with_label(8, "isolated");
+
+        let forest = g.spanning_forest();
+        assert_eq!(forest.number_of_vertices(), 8);
+        assert_eq!(forest.number_of_edges(), 8 - 3);
+        assert_eq!(forest.component_count(), 3);
+        assert!(forest.is_subgraph_of(&g));
+        assert_eq!(forest.get_label(&8).unwrap(), "isolated");
+        for vertex in 1..=8 {
+            assert_eq!(forest.shortest_cycle_through(&vertex), Ok(None));
+        }
+    }
+
+    #[test]
+    fn terminals_given_dag_return_roots_and_leaves() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_path(&[1, 3, 4]).unwrap();
+        g.add_path(&[2, 3, 5]).unwrap();
+        g.add_vertex(6);
+        g.add_vertex(7);
+        g.add_edge(7, 1).unwrap();
+        g.remove_edge(&7, &1).unwrap();
+
+        let (mut sources, mut sinks) = g.terminals();
+        sources.sort();
+        sinks.sort();
+        assert_eq!(sources, vec![1, 2, 6, 7]);
+        assert_eq!(sinks, vec![4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn shortest_path_given_reachable_goal_return_fewest_edges() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_path(&[1, 2, 3, 4, 5]).unwrap();
+        g.add_vertex(6);
+        g.add_edge(2, 6).unwrap();
+        g.add_edge(6, 5).unwrap();
+
+        assert_eq!(g.shortest_path(&1, &5), Ok(Some(vec![1, 2, 6, 5])));
+        assert_eq!(g.shortest_path(&3, &5), Ok(Some(vec![3, 4, 5])));
+    }
+
+    #[test]
+    fn shortest_path_given_unreachable_goal_return_none() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_path(&[1, 2, 3]).unwrap();
+        g.add_vertex(4);
+
+        assert_eq!(g.shortest_path(&3, &1), Ok(None));
+        assert_eq!(g.shortest_path(&1, &4), Ok(None));
+        assert_eq!(g.shortest_path(&1, &5), Err(GraphError::NoSuchVertex));
+    }
+
+    #[test]
+    fn shortest_path_given_same_start_and_goal_return_single_vertex() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_vertex(1);
+
+        assert_eq!(g.shortest_path(&1, &1), Ok(Some(vec![1])));
+    }
+
+    #[test]
+    fn min_edges_to_isolate_given_three_incident_edges_return_three() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_path(&[1, 2, 3]).unwrap();
+        g.add_vertex(4);
+        g.add_edge(2, 4).unwrap();
+        g.add_edge(3, 4).unwrap();
+
+        assert_eq!(g.min_edges_to_isolate(&2), Ok(3));
+        g.add_edge(2, 2).unwrap();
+        assert_eq!(g.min_edges_to_isolate(&2), Ok(4));
+        assert_eq!(g.min_edges_to_isolate(&5), Err(GraphError::NoSuchVertex));
+    }
+
+    #[test]
+    fn topological_sort_given_chain_return_chain_order() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_path(&[3, 1, 4, 2]).unwrap();
+
+        assert_eq!(g.topological_sort(), Ok(vec![3, 1, 4, 2]));
+    }
+
+    #[test]
+    fn topological_sort_given_diamond_return_valid_order() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_path(&[1, 2, 4]).unwrap();
+        g.add_path(&[1, 3, 4]).unwrap();
+
+        let order = g.topological_sort().unwrap();
+        assert_eq!(order.len(), 4);
+        let position: HashMap<u32, usize> = order.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+        for (outbound, incoming) in &g.edges {
+            assert!(position[outbound] < position[incoming]);
+        }
+    }
+
+    #[test]
+    fn topological_sort_given_cycle_return_cycle_detected() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_path(&[1, 2, 3, 4]).unwrap();
+        g.add_edge(4, 2).unwrap();
+
+        assert_eq!(g.topological_sort(), Err(GraphError::CycleDetected));
+    }
+
+    #[test]
+    fn transitivity_given_triangle_and_path_return_one_and_zero() {
+        let mut triangle: Graph<u32> = Graph::new();
+        triangle.add_cycle(&[1, 2, 3]).unwrap();
+        assert_eq!(triangle.transitivity(), 1.0);
+
+        let mut path: Graph<u32> = Graph::new();
+        path.add_path(&[1, 2, 3]).unwrap();
+        assert_eq!(path.transitivity(), 0.0);
+
+        let empty: Graph<u32> = Graph::new();
+        assert_eq!(empty.transitivity(), 0.0);
+
+        triangle.add_vertex(4);
+        triangle.add_edge(3, 4).unwrap();
+        assert_eq!(triangle.transitivity(), 0.6);
+    }
+
+    #[test]
+    fn has_cycle_given_acyclic_graph_return_false() {
+        let mut g: Graph<u32> = Graph::new();
+        assert!(!g.has_cycle());
+        g.add_path(&[1, 2, 4]).unwrap();
+        g.add_path(&[1, 3, 4]).unwrap();
+        g.add_path(&[5, 6]).unwrap();
+
+        assert!(!g.has_cycle());
+    }
+
+    #[test]
+    fn has_cycle_given_back_edge_return_true() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_path(&[1, 2]).unwrap();
+        g.add_path(&[3, 4, 5, 6]).unwrap();
+        g.add_edge(6, 4).unwrap();
+
+        assert!(g.has_cycle());
+    }
+
+    #[test]
+    fn has_cycle_given_self_loop_return_true() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_vertex(1);
+        g.add_edge(1, 1).unwrap();
+
+        assert!(g.has_cycle());
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn par_reachable_set_given_large_graph_match_sequential_search() {
+        let mut g: Graph<u32> = Graph::new();
+        for i in 0..5000 {
+            g.add_vertex(i);
+        }
+        for i in 0..5000 {
+            if i % 7 != 0 {
+                g.add_edge(i, (i * 3 + 1) % 5000).unwrap();
+                g.add_edge(i, (i + 11) % 5000).unwrap();
+            }
+        }
+
+        for start in [1, 14, 4999] {
+            let sequential: HashSet<u32> = g.bfs(&start).unwrap().into_iter().collect();
+            assert_eq!(g.par_reachable_set(&start).unwrap(), sequential);
+        }
+        assert_eq!(g.par_reachable_set(&5000), Err(GraphError::NoSuchVertex));
+    }
+
+    #[test]
+    fn random_spanning_tree_given_seed_return_tree_spanning_all_vertices() {
+        let mut g: Graph<u32> = Graph::new();
+        for i in 0..30 {
+            g.add_vertex(i);
+        }
+        for i in 0..30 {
+            g.add_edge(i, (i + 1) % 30).unwrap();
+            g.add_edge((i * 7) % 30, i).unwrap();
+        }
+
+        let mut rng = StdRng::seed_from_u64(3);
+        let tree = g.random_spanning_tree(&mut rng).unwrap();
+        assert_eq!(tree.number_of_vertices(), 30);
+        assert_eq!(tree.number_of_edges(), 29);
+        assert_eq!(tree.component_count(), 1);
+        assert!(tree.is_subgraph_of(&g));
+
+        g.add_vertex(30);
+        assert!(g.random_spanning_tree(&mut rng).is_none());
+    }
+
+    #[test]
+    fn weakly_connected_components_given_two_clusters_return_both() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_cycle(&[1, 2, 3]).unwrap();
+        g.add_path(&[5, 4]).unwrap();
+        g.add_path(&[6, 4]).unwrap();
+        g.add_vertex(7);
+
+        let mut components: Vec<Vec<u32>> = g.weakly_connected_components().into_iter()
+            .map(|mut component| {
+                component.sort();
+                component
+            })
+            .collect();
+        components.sort();
+        assert_eq!(components, vec![vec![1, 2, 3], vec![4, 5, 6], vec![7]]);
+
+        g.add_edge(4, 2).unwrap();
+        g.remove_vertex(&7).unwrap();
+        let components = g.weakly_connected_components();
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0].len(), 6);
+    }
+
+    #[test]
+    fn transpose_given_digraph_flip_edges_and_keep_labels() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_vertex_with_label(1, "A");
+        g.add_vertex_with_label(2, "B");
+        g.add_vertex(3);
+        g.add_edge(1, 2).unwrap();
+        g.add_weighted_edge(2, 3, 5).unwrap();
+        g.add_edge(3, 3).unwrap();
+
+        let transposed = g.transpose();
+        assert!(transposed.is_edge_in_graph(2, 1));
+        assert!(transposed.is_edge_in_graph(3, 2));
+        assert!(transposed.is_edge_in_graph(3, 3));
+        assert!(!transposed.is_edge_in_graph(1, 2));
+        assert_eq!(transposed.number_of_edges(), 3);
+        assert_eq!(transposed.edge_weight(&3, &2), Some(&5));
+        assert_eq!(transposed.get_label(&1).unwrap(), "A");
+        assert_eq!(transposed.get_label(&2).unwrap(), "B");
+        assert_eq!(transposed.in_neighbours(&1).collect::<Vec<&u32>>(), vec![&2]);
+
+        assert!(transposed.transpose() == g);
+    }
+
+    #[test]
+    fn out_neighbours_excluding_given_subset_return_remaining_neighbours() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_vertex(1);
+        for i in 2..=5 {
+            g.add_vertex(i);
+            g.add_edge(1, i).unwrap();
+        }
+        g.add_edge(5, 1).unwrap();
+
+        let exclude: HashSet<u32> = [2, 4, 1].into_iter().collect();
+        let mut neighbours = g.out_neighbours_excluding(&1, &exclude).unwrap();
+        neighbours.sort();
+        assert_eq!(neighbours, vec![3, 5]);
+        assert_eq!(g.out_neighbours_excluding(&5, &exclude), Ok(vec![]));
+        assert_eq!(g.out_neighbours_excluding(&6, &exclude), Err(GraphError::NoSuchVertex));
+    }
+
+    #[test]
+    fn wiener_index_given_connected_graph_return_sum_of_distances() {
+        let mut g: Graph<u32> = Graph::new();
+        assert_eq!(g.wiener_index(), Some(0));
+        g.add_cycle(&[1, 2, 3]).unwrap();
+        assert_eq!(g.wiener_index(), Some(9));
+
+        g.add_edge(1, 3).unwrap();
+        assert_eq!(g.wiener_index(), Some(8));
+
+        g.add_vertex(4);
+        g.add_edge(3, 4).unwrap();
+        assert_eq!(g.wiener_index(), None);
+    }
+
+    #[test]
+    fn to_dot_given_labelled_graph_contain_node_and_edge_lines() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_vertex_with_label(1, "start \"A\"");
+        g.add_vertex(2);
+        g.add_edge(1, 2).unwrap();
+        g.add_edge(2, 2).unwrap();
+
+        let dot = g.to_dot();
+        assert!(dot.starts_with("digraph {\n"));
+        assert!(dot.ends_with('}'));
+        assert!(dot.contains("    \"1\" [label=\"start \\\"A\\\"\"];\n"));
+        assert!(dot.contains("    \"2\" [label=\"2\"];\n"));
+        assert!(dot.contains("    \"1\" -> \"2\";\n"));
+        assert!(dot.contains("    \"2\" -> \"2\";\n"));
+        assert_eq!(dot.lines().count(), 6);
+    }
+
+    #[test]
+    fn set_value_given_vertex_in_graph_set_and_overwrite_value() {
+        let mut g: Graph<u32, i64> = Graph::new();
+        g.add_vertex(1);
+        g.add_vertex_with_label(2, "B");
+        assert_eq!(g.get_value(&1), None);
+
+        g.set_value(&1, 10).unwrap();
+        assert_eq!(g.get_value(&1), Some(&10));
+
+        g.set_value(&1, -3).unwrap();
+        *g.get_value_mut(&1).unwrap() *= 2;
+        assert_eq!(g.get_value(&1), Some(&-6));
+        assert_eq!(g.get_label(&2).unwrap(), "B");
+
+        g.remove_vertex(&1).unwrap();
+        assert_eq!(g.get_value(&1), None);
+    }
+
+    #[test]
+    fn set_value_given_vertex_not_in_graph_return_no_such_vertex() {
+        let mut g: Graph<u32, String> = Graph::new();
+        g.add_vertex(1);
+
+        assert_eq!(g.set_value(&2, "rating".to_owned()), Err(GraphError::NoSuchVertex));
+        assert_eq!(g.get_value(&2), None);
+        assert_eq!(g.get_value_mut(&2), None);
+    }
+
+    #[test]
+    fn sample_vertex_by_degree_given_seed_prefer_high_degree_vertices() {
+        let mut g: Graph<u32> = Graph::new();
+        let mut rng = StdRng::seed_from_u64(11);
+        assert_eq!(g.sample_vertex_by_degree(&mut rng), None);
+        g.add_vertex(0);
+        for i in 1..=9 {
+            g.add_vertex(i);
+            g.add_edge(0, i).unwrap();
+        }
+        g.add_vertex(10);
+
+        let mut hub_draws = 0;
+        for _ in 0..1000 {
+            let vertex = g.sample_vertex_by_degree(&mut rng).unwrap();
+            assert_ne!(vertex, 10);
+            if vertex == 0 {
+                hub_draws += 1;
+            }
+        }
+        // The hub has half of the total degree, every leaf only a eighteenth
+        assert!(hub_draws > 400 && hub_draws < 600);
+    }
+
+    #[test]
+    fn max_out_degree_at_most_given_hub_compare_against_limit() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_vertex(0);
+        for i in 1..=4 {
+            g.add_vertex(i);
+            g.add_edge(0, i).unwrap();
+        }
+        g.add_edge(1, 2).unwrap();
+
+        assert!(!g.max_out_degree_at_most(3));
+        assert!(g.max_out_degree_at_most(4));
+        assert!(Graph::<u32>::new().max_out_degree_at_most(0));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip_given_json_return_equal_graph() {
+        let mut g: Graph<(u8, u8), f64> = Graph::new();
+        g.add_vertex_with_label((0, 0), "start");
+        g.add_vertex((0, 1));
+        g.add_vertex((1, 1));
+        g.add_edge((0, 0), (0, 1)).unwrap();
+        g.add_weighted_edge((0, 1), (1, 1), 3).unwrap();
+        g.add_edge((1, 1), (0, 0)).unwrap();
+        g.set_value(&(1, 1), 0.5).unwrap();
+
+        let json = serde_json::to_string(&g).unwrap();
+        let loaded: Graph<(u8, u8), f64> = serde_json::from_str(&json).unwrap();
+
+        assert!(loaded == g);
+        assert_eq!(loaded.out_neighbours(&(0, 1)).collect::<Vec<&(u8, u8)>>(), vec![&(1, 1)]);
+        assert_eq!(loaded.in_neighbours(&(0, 0)).collect::<Vec<&(u8, u8)>>(), vec![&(1, 1)]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_given_edge_with_unknown_vertex_return_error() {
+        let json = r#"{"vertices":[1],"edges":[[1,2]],"vertex_labels":[],"vertex_values":[],"edge_weights":[]}"#;
+        assert!(serde_json::from_str::<Graph<u32>>(json).is_err());
+    }
+
+    #[test]
+    fn vertices_given_inserted_vertices_return_all_of_them() {
+        let mut g: Graph<u32> = Graph::new();
+        for i in [3, 1, 4, 5, 9] {
+            g.add_vertex(i);
+        }
+        g.add_edge(3, 1).unwrap();
+
+        let vertices: HashSet<u32> = g.vertices().copied().collect();
+        assert_eq!(vertices, HashSet::from([1, 3, 4, 5, 9]));
+        assert_eq!(Graph::<u32>::new().vertices().count(), 0);
+    }
+
+    #[test]
+    fn edge_connectivity_given_two_edge_disjoint_paths_return_two() {
+        let mut g: Graph<u32> = Graph::new();
+        for i in 1..=5 {
+            g.add_vertex(i);
+        }
+        g.add_path(&[1, 2, 3, 5]).unwrap();
+        g.add_weighted_edge(1, 4, 10).unwrap();
+        g.add_weighted_edge(4, 5, 10).unwrap();
+        g.add_edge(2, 4).unwrap();
+
+        assert_eq!(g.edge_connectivity(&1, &5), Ok(2));
+        assert_eq!(g.edge_connectivity(&5, &1), Ok(0));
+        assert_eq!(g.edge_connectivity(&1, &6), Err(GraphError::NoSuchVertex));
+    }
+
+    #[test]
+    fn to_sorted_adjacency_string_given_small_graph_return_sorted_lines() {
+        let mut g: Graph<u32> = Graph::new();
+        for i in [3, 1, 2, 4] {
+            g.add_vertex(i);
+        }
+        g.add_edge(1, 3).unwrap();
+        g.add_edge(1, 2).unwrap();
+        g.add_edge(3, 1).unwrap();
+        g.add_edge(2, 4).unwrap();
+
+        assert_eq!(g.to_sorted_adjacency_string(), "1: 2 3\n2: 4\n3: 1\n4:");
+    }
+
+    #[test]
+    fn edges_given_inserted_edges_return_each_once() {
+        let mut g: Graph<u32> = Graph::new();
+        for i in 1..=3 {
+            g.add_vertex(i);
+        }
+        g.add_edge(1, 2).unwrap();
+        g.add_edge(2, 1).unwrap();
+        g.add_edge(2, 3).unwrap();
+        g.add_edge(2, 3).unwrap();
+
+        let edges: Vec<(u32, u32)> = g.edges().copied().collect();
+        assert_eq!(edges.len(), 3);
+        assert_eq!(edges.into_iter().collect::<HashSet<(u32, u32)>>(), HashSet::from([(1, 2), (2, 1), (2, 3)]));
+    }
+
+    #[test]
+    fn approx_vertex_cover_given_path_return_cover_within_factor_two() {
+        let mut g: Graph<u32> = Graph::new();
+        for i in 1..=5 {
+            g.add_vertex(i);
+        }
+        // Path 1 - 2 - 3 - 4 - 5 with minimum vertex cover {2, 4}
+        g.add_path(&[1, 2, 3, 4, 5]).unwrap();
+        g.add_edge(3, 2).unwrap();
+
+        let cover = g.approx_vertex_cover();
+        for (outbound, incoming) in g.edges() {
+            assert!(cover.contains(outbound) || cover.contains(incoming));
+        }
+        assert!(cover.len() <= 2 * 2);
+        assert!(Graph::<u32>::new().approx_vertex_cover().is_empty());
+    }
+
+    #[test]
+    fn clear_given_populated_graph_return_empty_graph() {
+        let mut g: Graph<u32, u8> = Graph::new();
+        g.add_vertex_with_label(1, "one");
+        g.add_vertex(2);
+        g.add_weighted_edge(1, 2, 5).unwrap();
+        g.add_edge(2, 1).unwrap();
+        g.set_value(&2, 7).unwrap();
+
+        g.clear();
+        assert_eq!(g.number_of_vertices(), 0);
+        assert_eq!(g.number_of_edges(), 0);
+        assert_eq!(g.out_neighbours(&1).count(), 0);
+        assert_eq!(g.in_neighbours(&1).count(), 0);
+        assert_eq!(g.get_label(&1), None);
+        assert_eq!(g.get_value(&2), None);
+        assert_eq!(g.component_count(), 0);
+        assert!(g == Graph::new());
+
+        g.add_vertex(1);
+        assert_eq!(g.component_count(), 1);
+    }
+
+    #[test]
+    fn greedy_dominating_set_given_star_return_center() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_vertex(0);
+        for i in 1..=5 {
+            g.add_vertex(i);
+            g.add_edge(i, 0).unwrap();
+        }
+
+        assert_eq!(g.greedy_dominating_set(), HashSet::from([0]));
+
+        g.add_vertex(6);
+        assert_eq!(g.greedy_dominating_set(), HashSet::from([0, 6]));
+    }
+
+    #[test]
+    fn subgraph_given_vertex_subset_drop_cross_boundary_edges() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_vertex_with_label(1, "one");
+        for i in 2..=4 {
+            g.add_vertex(i);
+        }
+        g.add_cycle(&[1, 2, 3]).unwrap();
+        g.add_weighted_edge(3, 4, 2).unwrap();
+        g.add_weighted_edge(2, 1, 6).unwrap();
+
+        let subgraph = g.subgraph(&HashSet::from([1, 2, 4, 9]));
+        assert_eq!(subgraph.vertices().copied().collect::<HashSet<u32>>(), HashSet::from([1, 2, 4]));
+        assert_eq!(subgraph.edges().copied().collect::<HashSet<(u32, u32)>>(), HashSet::from([(1, 2), (2, 1)]));
+        assert_eq!(subgraph.get_label(&1), Some(&String::from("one")));
+        assert_eq!(subgraph.edge_weight(&2, &1), Some(&6));
+        assert_eq!(subgraph.out_neighbours(&2).collect::<Vec<&u32>>(), vec![&1]);
+        assert_eq!(subgraph.in_neighbours(&4).count(), 0);
+    }
+
+    #[test]
+    fn shortest_path_length_given_various_goals_return_edge_count() {
+        let mut g: Graph<u32> = Graph::new();
+        for i in 1..=5 {
+            g.add_vertex(i);
+        }
+        g.add_path(&[1, 2, 3, 4]).unwrap();
+        g.add_edge(1, 3).unwrap();
+
+        assert_eq!(g.shortest_path_length(&1, &4), Ok(Some(2)));
+        assert_eq!(g.shortest_path_length(&2, &2), Ok(Some(0)));
+        assert_eq!(g.shortest_path_length(&4, &1), Ok(None));
+        assert_eq!(g.shortest_path_length(&1, &5), Ok(None));
+        assert_eq!(g.shortest_path_length(&1, &6), Err(GraphError::NoSuchVertex));
+    }
+
+    #[test]
+    fn add_undirected_edge_given_two_vertices_add_both_directions() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_vertex(1);
+        g.add_vertex(2);
+
+        assert_eq!(g.add_undirected_edge(1, 2), Ok(()));
+        assert!(g.is_edge_in_graph(1, 2));
+        assert!(g.is_edge_in_graph(2, 1));
+        assert_eq!(g.out_neighbours(&2).collect::<Vec<&u32>>(), vec![&1]);
+        assert_eq!(g.in_neighbours(&2).collect::<Vec<&u32>>(), vec![&1]);
+
+        assert_eq!(g.add_undirected_edge(1, 1), Ok(()));
+        assert_eq!(g.number_of_edges(), 3);
+        assert_eq!(g.add_undirected_edge(1, 3), Err(GraphError::NoSuchVertex));
+        assert_eq!(g.number_of_edges(), 3);
+    }
+
+    #[test]
+    fn remove_undirected_edge_given_undirected_edge_remove_both_directions() {
+        let mut g: Graph<u32> = Graph::new();
+        for i in 1..=3 {
+            g.add_vertex(i);
+        }
+        g.add_undirected_edge(1, 2).unwrap();
+        g.add_edge(2, 3).unwrap();
+
+        assert_eq!(g.remove_undirected_edge(&2, &3), Err(GraphError::NoSuchEdge));
+        assert!(g.is_edge_in_graph(2, 3));
+        assert_eq!(g.remove_undirected_edge(&2, &4), Err(GraphError::NoSuchVertex));
+
+        assert_eq!(g.remove_undirected_edge(&2, &1), Ok(()));
+        assert!(!g.is_edge_in_graph(1, 2));
+        assert!(!g.is_edge_in_graph(2, 1));
+        assert_eq!(g.out_neighbours(&1).count(), 0);
+        assert_eq!(g.in_neighbours(&2).count(), 0);
+        assert_eq!(g.number_of_edges(), 1);
+    }
+
+    #[test]
+    fn edges_incident_to_set_given_subset_return_incident_edges_once() {
+        let mut g: Graph<u32> = Graph::new();
+        for i in 1..=5 {
+            g.add_vertex(i);
+        }
+        g.add_cycle(&[1, 2, 3]).unwrap();
+        g.add_edge(3, 4).unwrap();
+        g.add_edge(4, 5).unwrap();
+        g.add_edge(2, 2).unwrap();
+
+        let edges = g.edges_incident_to_set(&HashSet::from([1, 2]));
+        assert_eq!(edges.len(), 4);
+        assert_eq!(edges.into_iter().collect::<HashSet<(u32, u32)>>(), HashSet::from([(1, 2), (2, 3), (3, 1), (2, 2)]));
+        assert!(g.edges_incident_to_set(&HashSet::new()).is_empty());
+    }
+
+    #[test]
+    fn union_given_overlapping_graphs_combine_vertices_and_edges() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_vertex_with_label(1, "one");
+        g.add_vertex_with_label(2, "two");
         g.add_vertex(3);
+        g.add_path(&[1, 2, 3]).unwrap();
 
-        g.set_label(&3 ,"C").unwrap();
+        let mut other: Graph<u32> = Graph::new();
+        other.add_vertex_with_label(2, "second");
+        other.add_vertex(3);
+        other.add_vertex(4);
+        other.add_edge(2, 3).unwrap();
+        other.add_weighted_edge(3, 4, 5).unwrap();
 
+        g.union(&other);
+        assert_eq!(g.number_of_vertices(), 4);
+        assert_eq!(g.number_of_edges(), 3);
+        assert_eq!(g.get_label(&1), Some(&String::from("one")));
+        assert_eq!(g.get_label(&2), Some(&String::from("second")));
+        assert_eq!(g.edge_weight(&3, &4), Some(&5));
+        assert_eq!(g.out_neighbours(&2).collect::<Vec<&u32>>(), vec![&3]);
+        assert_eq!(g.in_neighbours(&4).collect::<Vec<&u32>>(), vec![&3]);
+        assert_eq!(g.component_count(), 1);
+    }
 
-        assert_eq!(g.get_label(&3).unwrap() , "C");
-        assert_eq!(g.set_label(&4, ""), Err(GraphError::NoSuchVertex));
-    } 
+    #[test]
+    fn dijkstra_given_cheaper_longer_path_return_cheapest_path() {
+        let mut g: Graph<u32> = Graph::new();
+        for i in 1..=6 {
+            g.add_vertex(i);
+        }
+        g.add_weighted_edge(1, 4, 10).unwrap();
+        g.add_edge(1, 2).unwrap();
+        g.add_weighted_edge(2, 3, 2).unwrap();
+        g.add_weighted_edge(3, 4, 3).unwrap();
+        g.add_weighted_edge(2, 4, 7).unwrap();
+        g.add_edge(4, 5).unwrap();
+
+        assert_eq!(g.dijkstra(&1, &5), Ok(Some((vec![1, 2, 3, 4, 5], 7))));
+        assert_eq!(g.dijkstra(&3, &3), Ok(Some((vec![3], 0))));
+        assert_eq!(g.dijkstra(&5, &1), Ok(None));
+        assert_eq!(g.dijkstra(&1, &6), Ok(None));
+        assert_eq!(g.dijkstra(&1, &7), Err(GraphError::NoSuchVertex));
+    }
+
+    #[test]
+    fn global_efficiency_given_complete_graph_and_path_return_expected_values() {
+        let mut complete: Graph<u32> = Graph::new();
+        for i in 1..=4 {
+            complete.add_vertex(i);
+        }
+        for i in 1..=4 {
+            for j in 1..=4 {
+                if i != j {
+                    complete.add_edge(i, j).unwrap();
+                }
+            }
+        }
+        assert!((complete.global_efficiency() - 1.0).abs() < 1e-9);
+
+        // Directed path 1 -> 2 -> 3 reaches 1 / 1 + 1 / 1 + 1 / 2 out of 6 ordered pairs
+        let mut path: Graph<u32> = Graph::new();
+        path.add_path(&[1, 2, 3]).unwrap();
+        assert!((path.global_efficiency() - 2.5 / 6.0).abs() < 1e-9);
+        assert_eq!(Graph::<u32>::new().global_efficiency(), 0.0);
+    }
+
+    #[test]
+    fn undirected_edge_count_given_mutual_pair_and_self_loop_return_collapsed_count() {
+        let mut g: Graph<u32> = Graph::new();
+        for i in 1..=3 {
+            g.add_vertex(i);
+        }
+        g.add_undirected_edge(1, 2).unwrap();
+        g.add_edge(2, 3).unwrap();
+        g.add_edge(3, 3).unwrap();
+
+        assert_eq!(g.number_of_edges(), 4);
+        assert_eq!(g.undirected_edge_count(), 3);
+    }
+
+    #[test]
+    fn adjacency_matrix_given_small_graph_return_matching_cells() {
+        let mut g: Graph<u32> = Graph::new();
+        for i in 1..=4 {
+            g.add_vertex(i);
+        }
+        g.add_edge(1, 2).unwrap();
+        g.add_edge(3, 3).unwrap();
+        g.add_undirected_edge(2, 4).unwrap();
+
+        let (order, matrix) = g.adjacency_matrix();
+        let index = |vertex: u32| order.iter().position(|v| *v == vertex).unwrap();
+        assert_eq!(order.len(), 4);
+        assert!(matrix[index(1)][index(2)]);
+        assert!(!matrix[index(2)][index(1)]);
+        assert!(matrix[index(3)][index(3)]);
+        assert!(matrix[index(2)][index(4)] && matrix[index(4)][index(2)]);
+        assert_eq!(matrix.iter().flatten().filter(|entry| **entry).count(), 4);
+    }
+
+    #[test]
+    fn map_edges_given_swapped_endpoints_return_reversed_graph() {
+        let mut g: Graph<u32> = Graph::new();
+        for i in 1..=4 {
+            g.add_vertex(i);
+        }
+        g.add_path(&[1, 2, 3]).unwrap();
+        g.add_edge(3, 3).unwrap();
+
+        let reversed = g.map_edges(|(a, b)| (*b, *a));
+        assert_eq!(reversed.vertices().copied().collect::<HashSet<u32>>(), HashSet::from([1, 2, 3]));
+        assert_eq!(reversed.edges().copied().collect::<HashSet<(u32, u32)>>(), HashSet::from([(2, 1), (3, 2), (3, 3)]));
+        assert_eq!(reversed.out_neighbours(&3).count(), 2);
+
+        // Mapping every vertex to whether it is even collapses (1, 2) and (1, 4) into one edge
+        g.add_edge(1, 4).unwrap();
+        let parity = g.map_edges(|(a, b)| (*a % 2 == 0, *b % 2 == 0));
+        assert_eq!(parity.number_of_vertices(), 2);
+        assert_eq!(parity.number_of_edges(), 3);
+        assert_eq!(parity.edges().copied().collect::<HashSet<(bool, bool)>>(), HashSet::from([(false, true), (true, false), (false, false)]));
+    }
+
+    #[test]
+    fn reachable_from_given_dag_and_cycle_return_reachable_set() {
+        let mut g: Graph<u32> = Graph::new();
+        for i in 1..=6 {
+            g.add_vertex(i);
+        }
+        g.add_path(&[1, 2, 4]).unwrap();
+        g.add_edge(1, 3).unwrap();
+        g.add_edge(3, 4).unwrap();
+
+        assert_eq!(g.reachable_from(&1), Ok(HashSet::from([2, 3, 4])));
+        assert_eq!(g.reachable_from(&4), Ok(HashSet::new()));
+
+        g.add_cycle(&[4, 5]).unwrap();
+        assert_eq!(g.reachable_from(&4), Ok(HashSet::from([4, 5])));
+        assert_eq!(g.reachable_from(&2), Ok(HashSet::from([4, 5])));
+        assert_eq!(g.reachable_from(&7), Err(GraphError::NoSuchVertex));
+    }
+
+    #[test]
+    fn approximate_center_given_path_return_middle_vertex() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_path(&[1, 2, 3, 4, 5, 6, 7]).unwrap();
+        assert_eq!(g.approximate_center(), Some(4));
+
+        g.add_vertex(8);
+        g.add_edge(8, 7).unwrap();
+        assert!([4, 5].contains(&g.approximate_center().unwrap()));
+        assert_eq!(Graph::<u32>::new().approximate_center(), None);
+    }
+
+    #[test]
+    fn has_path_given_various_goals_return_reachability() {
+        let mut g: Graph<u32> = Graph::new();
+        for i in 1..=4 {
+            g.add_vertex(i);
+        }
+        g.add_path(&[1, 2, 3]).unwrap();
+
+        assert_eq!(g.has_path(&1, &3), Ok(true));
+        assert_eq!(g.has_path(&3, &1), Ok(false));
+        assert_eq!(g.has_path(&1, &4), Ok(false));
+        assert_eq!(g.has_path(&4, &4), Ok(true));
+        assert_eq!(g.has_path(&1, &5), Err(GraphError::NoSuchVertex));
+    }
+
+    #[test]
+    fn remove_all_edges_between_given_duplicate_entries_remove_all_of_them() {
+        let mut g: Graph<u32> = Graph::new();
+        for i in 1..=3 {
+            g.add_vertex(i);
+        }
+        g.add_weighted_edge(1, 2, 4).unwrap();
+        g.add_edge(1, 3).unwrap();
+        g.add_edge(2, 1).unwrap();
+        // Simulate the duplicate adjacency entries of repeated add_edge calls
+        g.outbound_table.get_mut(&1).unwrap().push(2);
+        g.inbound_table.get_mut(&2).unwrap().push(1);
+
+        assert_eq!(g.remove_all_edges_between(&1, &2), Ok(()));
+        assert!(!g.is_edge_in_graph(1, 2));
+        assert_eq!(g.edge_weight(&1, &2), None);
+        assert_eq!(g.out_neighbours(&1).collect::<Vec<&u32>>(), vec![&3]);
+        assert_eq!(g.in_neighbours(&2).count(), 0);
+        assert!(g.is_edge_in_graph(2, 1));
+        assert_eq!(g.remove_all_edges_between(&1, &2), Ok(()));
+        assert_eq!(g.remove_all_edges_between(&1, &4), Err(GraphError::NoSuchVertex));
+    }
+
+    #[test]
+    fn bridge_count_given_cycles_joined_by_path_return_number_of_bridges() {
+        let mut g: Graph<u32> = Graph::new();
+        // Two triangles joined by the path 3 - 4 - 5 and a pendant vertex 8 at 6
+        g.add_cycle(&[1, 2, 3]).unwrap();
+        g.add_cycle(&[5, 6, 7]).unwrap();
+        g.add_path(&[3, 4, 5]).unwrap();
+        g.add_vertex(8);
+        g.add_undirected_edge(6, 8).unwrap();
+        g.add_vertex(9);
+
+        assert_eq!(g.bridge_count(), 3);
+
+        g.add_edge(4, 1).unwrap();
+        assert_eq!(g.bridge_count(), 2);
+        assert_eq!(Graph::<u32>::new().bridge_count(), 0);
+    }
+
+    #[test]
+    fn merge_set_given_three_vertices_preserve_external_connectivity() {
+        let mut g: Graph<u32> = Graph::new();
+        for i in 1..=6 {
+            g.add_vertex(i);
+        }
+        g.add_cycle(&[1, 2, 3]).unwrap();
+        g.add_edge(4, 1).unwrap();
+        g.add_weighted_edge(4, 2, 5).unwrap();
+        g.add_weighted_edge(3, 5, 2).unwrap();
+        g.add_edge(6, 5).unwrap();
+
+        assert_eq!(g.merge_set(&HashSet::from([1, 2, 7]), 10), Err(GraphError::NoSuchVertex));
+        assert_eq!(g.merge_set(&HashSet::from([1, 2, 3]), 10), Ok(()));
+
+        assert_eq!(g.vertices().copied().collect::<HashSet<u32>>(), HashSet::from([4, 5, 6, 10]));
+        assert_eq!(g.edges().copied().collect::<HashSet<(u32, u32)>>(), HashSet::from([(4, 10), (10, 5), (6, 5)]));
+        assert_eq!(g.edge_weight(&4, &10), Some(&1));
+        assert_eq!(g.edge_weight(&10, &5), Some(&2));
+        assert_eq!(g.out_neighbours(&4).collect::<Vec<&u32>>(), vec![&10]);
+        assert_eq!(g.in_neighbours(&10).collect::<Vec<&u32>>(), vec![&4]);
+        assert_eq!(g.component_count(), 1);
+    }
+
+    #[test]
+    fn density_given_small_graphs_return_expected_values() {
+        let mut g: Graph<u32> = Graph::new();
+        assert_eq!(g.density(), 0.0);
+
+        g.add_vertex(1);
+        g.add_edge(1, 1).unwrap();
+        assert_eq!(g.density(), 0.0);
+
+        g.add_vertex(2);
+        g.add_vertex(3);
+        g.add_undirected_edge(1, 2).unwrap();
+        g.add_undirected_edge(2, 3).unwrap();
+        g.add_undirected_edge(3, 1).unwrap();
+        assert!((g.density() - 1.0).abs() < 1e-9);
+
+        g.remove_edge(&1, &2).unwrap();
+        assert!((g.density() - 5.0 / 6.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn self_loops_given_mixed_edges_return_vertices_with_self_loop() {
+        let mut g: Graph<u32> = Graph::new();
+        for i in 1..=4 {
+            g.add_vertex(i);
+        }
+        g.add_edge(1, 1).unwrap();
+        g.add_edge(1, 2).unwrap();
+        g.add_undirected_edge(2, 3).unwrap();
+        g.add_edge(3, 3).unwrap();
+        g.add_edge(3, 3).unwrap();
+
+        let mut self_loops = g.self_loops();
+        self_loops.sort();
+        assert_eq!(self_loops, vec![1, 3]);
+        assert!(Graph::<u32>::new().self_loops().is_empty());
+    }
+
+    #[test]
+    fn layered_layout_given_diamond_return_layers_by_generation() {
+        let mut g: Graph<u32> = Graph::new();
+        for i in 1..=5 {
+            g.add_vertex(i);
+        }
+        // Diamond 1 -> {2, 3} -> 4 with an additional shortcut 1 -> 4 and an isolated vertex 5
+        g.add_path(&[1, 2, 4]).unwrap();
+        g.add_path(&[1, 3, 4]).unwrap();
+        g.add_edge(1, 4).unwrap();
+
+        let layout = g.layered_layout().unwrap();
+        assert_eq!(layout.len(), 5);
+        assert_eq!(layout[&1].0, 0);
+        assert_eq!(layout[&5].0, 0);
+        assert_eq!(layout[&2].0, 1);
+        assert_eq!(layout[&3].0, 1);
+        assert_eq!(layout[&4], (2, 0));
+        assert_eq!(HashSet::from([layout[&2].1, layout[&3].1]), HashSet::from([0, 1]));
+        assert_eq!(HashSet::from([layout[&1].1, layout[&5].1]), HashSet::from([0, 1]));
+
+        g.add_edge(4, 1).unwrap();
+        assert_eq!(g.layered_layout(), Err(GraphError::CycleDetected));
+    }
+
+    #[test]
+    fn cycle_basis_given_two_independent_cycles_return_basis_of_size_two() {
+        let mut g: Graph<u32> = Graph::new();
+        // Square 1 - 2 - 3 - 4 and triangle 4 - 5 - 6 sharing vertex 4, plus a tail 6 - 7
+        g.add_cycle(&[1, 2, 3, 4]).unwrap();
+        g.add_cycle(&[4, 5, 6]).unwrap();
+        g.add_path(&[6, 7]).unwrap();
+        g.add_edge(2, 1).unwrap();
+        g.add_edge(7, 7).unwrap();
+
+        let basis = g.cycle_basis();
+        assert_eq!(basis.len(), 2);
+        let mut cycles: Vec<HashSet<u32>> = basis.iter()
+            .map(|cycle| cycle.iter().copied().collect())
+            .collect();
+        cycles.sort_by_key(|cycle| cycle.len());
+        assert_eq!(cycles, vec![HashSet::from([4, 5, 6]), HashSet::from([1, 2, 3, 4])]);
+        for cycle in &basis {
+            for pair in cycle.windows(2) {
+                assert!(g.is_edge_in_graph(pair[0], pair[1]) || g.is_edge_in_graph(pair[1], pair[0]));
+            }
+            let (first, last) = (cycle[0], cycle[cycle.len() - 1]);
+            assert!(g.is_edge_in_graph(first, last) || g.is_edge_in_graph(last, first));
+        }
+        assert!(Graph::<u32>::new().cycle_basis().is_empty());
+    }
+
+    #[test]
+    fn dijkstra_given_overflowing_path_cost_ignore_that_path() {
+        let mut g: Graph<u32> = Graph::new();
+        for i in 1..=4 {
+            g.add_vertex(i);
+        }
+        g.add_weighted_edge(1, 2, u64::MAX - 1).unwrap();
+        g.add_weighted_edge(2, 3, 5).unwrap();
+        g.add_weighted_edge(1, 4, u64::MAX - 2).unwrap();
+        g.add_weighted_edge(4, 3, 2).unwrap();
+
+        assert_eq!(g.dijkstra(&1, &3), Ok(Some((vec![1, 4, 3], u64::MAX))));
+
+        g.add_weighted_edge(4, 3, 3).unwrap();
+        assert_eq!(g.dijkstra(&1, &3), Ok(None));
+        assert_eq!(g.dijkstra(&1, &2), Ok(Some((vec![1, 2], u64::MAX - 1))));
+    }
+
+    #[test]
+    fn random_subgraph_given_nan_or_infinite_fraction_do_not_panic() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_path(&[1, 2, 3]).unwrap();
+        let mut rng = StdRng::seed_from_u64(7);
+
+        assert_eq!(g.random_subgraph(f64::NAN, &mut rng).number_of_vertices(), 0);
+        assert!(g.random_subgraph(f64::INFINITY, &mut rng) == g);
+        assert_eq!(g.random_subgraph(f64::NEG_INFINITY, &mut rng).number_of_vertices(), 0);
+    }
+
+    #[test]
+    fn component_count_given_shared_reference_rebuild_after_removal() {
+        let mut g: Graph<u32> = Graph::new();
+        g.add_path(&[1, 2, 3]).unwrap();
+        g.remove_edge(&1, &2).unwrap();
+
+        let shared: &Graph<u32> = &g;
+        assert_eq!(shared.component_count(), 2);
+        assert_eq!(shared.clone().component_count(), 2);
+    }
+
+    #[test]
+    fn graph_is_sync_and_unwind_safe() {
+        fn assert_thread_safe<G: Send + Sync + UnwindSafe + RefUnwindSafe>() {}
+        assert_thread_safe::<Graph<u32>>();
+        assert_thread_safe::<Graph<(u8, u8), f64>>();
+
+        let mut g: Graph<u32> = Graph::new();
+        g.add_path(&[1, 2, 3]).unwrap();
+        g.remove_edge(&2, &3).unwrap();
+        let shared = std::sync::Arc::new(g);
+        let counted = std::thread::scope(|scope| {
+            scope.spawn(|| shared.component_count()).join().unwrap()
+        });
+        assert_eq!(counted, 2);
+        assert_eq!(std::panic::catch_unwind(|| shared.max_flow(&1, &2)).unwrap(), Ok(1));
+    }
+
+    #[test]
+    fn max_flow_given_capacities_near_u64_max_saturate_instead_of_overflowing() {
+        let mut g: Graph<u32> = Graph::new();
+        for i in 1..=4 {
+            g.add_vertex(i);
+        }
+        g.add_weighted_edge(1, 2, u64::MAX).unwrap();
+        g.add_weighted_edge(2, 4, u64::MAX).unwrap();
+        g.add_weighted_edge(1, 3, u64::MAX).unwrap();
+        g.add_weighted_edge(3, 4, u64::MAX).unwrap();
+        assert_eq!(g.max_flow(&1, &4), Ok(u64::MAX));
+
+        // Mutual edges add the pushed flow on top of a full reverse capacity
+        g.add_weighted_edge(2, 1, u64::MAX).unwrap();
+        g.add_weighted_edge(4, 2, u64::MAX).unwrap();
+        assert_eq!(g.max_flow(&1, &4), Ok(u64::MAX));
+        assert_eq!(g.max_flow(&1, &2), Ok(u64::MAX));
+    }
+
+    #[test]
+    fn min_cut_given_capacities_near_u64_max_return_saturated_value() {
+        let mut g: Graph<u32> = Graph::new();
+        for i in 1..=4 {
+            g.add_vertex(i);
+        }
+        g.add_weighted_edge(1, 2, u64::MAX).unwrap();
+        g.add_weighted_edge(2, 4, u64::MAX).unwrap();
+        g.add_weighted_edge(1, 3, u64::MAX).unwrap();
+        g.add_weighted_edge(3, 4, u64::MAX).unwrap();
+        g.add_weighted_edge(2, 1, u64::MAX).unwrap();
+
+        let (value, cut_edges) = g.min_cut(&1, &4).unwrap();
+        assert_eq!(Ok(value), g.max_flow(&1, &4));
+        assert_eq!(value, u64::MAX);
+        assert_eq!(cut_edges.into_iter().collect::<HashSet<(u32, u32)>>(), HashSet::from([(1, 2), (1, 3)]));
+    }
 }
@@ -1,2 +1,2 @@
 pub mod graph;
-
+mod union_find;
@@ -0,0 +1,133 @@
+// Disjoint-set forest over keys of generic type with union by size and path compression
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+#[derive(Debug, Clone)]
+/// Union-find structure which keeps track of the number of disjoint sets
+pub(crate) struct UnionFind<T: Eq + Hash + Copy> {
+    /// Mapping of elements to their parent element. Roots are their own parent
+    parents: HashMap<T, T>,
+
+    /// Mapping of root elements to the size of their set
+    sizes: HashMap<T, usize>,
+
+    /// Number of disjoint sets
+    count: usize,
+}
+
+impl<T: Eq + Hash + Copy> UnionFind<T> {
+    /// Creates a new empty union-find structure
+    pub(crate) fn new() -> UnionFind<T> {
+        UnionFind {
+            parents: HashMap::new(),
+            sizes: HashMap::new(),
+            count: 0,
+        }
+    }
+
+    /// Removes all elements while keeping the allocated memory for reuse
+    pub(crate) fn clear(&mut self) {
+        self.parents.clear();
+        self.sizes.clear();
+        self.count = 0;
+    }
+
+    /// Adds an element as a new singleton set if it was not present already
+    pub(crate) fn insert(&mut self, element: T) {
+        if let Entry::Vacant(entry) = self.parents.entry(element) {
+            entry.insert(element);
+            self.sizes.insert(element, 1);
+            self.count += 1;
+        }
+    }
+
+    /// Returns the root of the set containing the element, compressing the path on the way.
+    /// Elements which were not inserted are treated as singleton sets
+    pub(crate) fn find(&mut self, element: T) -> T {
+        let mut root = element;
+        while let Some(parent) = self.parents.get(&root) {
+            if *parent == root {
+                break;
+            }
+            root = *parent;
+        }
+
+        // Point every element on the path directly at the root
+        let mut current = element;
+        while current != root {
+            let parent = self.parents[&current];
+            self.parents.insert(current, root);
+            current = parent;
+        }
+        root
+    }
+
+    /// Merges the sets containing a and b, inserting them first if necessary. Returns whether
+    /// the sets were disjoint before
+    pub(crate) fn union(&mut self, a: T, b: T) -> bool {
+        self.insert(a);
+        self.insert(b);
+        let root_a = self.find(a);
+        let root_b = self.find(b);
+        if root_a == root_b {
+            return false;
+        }
+
+        let (small, large) = if self.sizes[&root_a] < self.sizes[&root_b] {
+            (root_a, root_b)
+        } else {
+            (root_b, root_a)
+        };
+        self.parents.insert(small, large);
+        let small_size = self.sizes.remove(&small).unwrap_or(1);
+        *self.sizes.entry(large).or_insert(1) += small_size;
+        self.count -= 1;
+        true
+    }
+
+    /// Returns the number of disjoint sets
+    pub(crate) fn count(&self) -> usize {
+        self.count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn union_given_overlapping_sets_count_disjoint_sets() {
+        let mut uf: UnionFind<u32> = UnionFind::new();
+        for i in 1..=5 {
+            uf.insert(i);
+        }
+        assert_eq!(uf.count(), 5);
+
+        assert!(uf.union(1, 2));
+        assert!(uf.union(3, 4));
+        assert!(uf.union(2, 4));
+        assert!(!uf.union(1, 3));
+
+        assert_eq!(uf.count(), 2);
+        assert_eq!(uf.find(1), uf.find(4));
+        assert_ne!(uf.find(1), uf.find(5));
+    }
+
+    #[test]
+    fn clear_given_merged_sets_return_empty_structure_keeping_capacity() {
+        let mut uf: UnionFind<u32> = UnionFind::new();
+        for i in 1..=100 {
+            uf.union(i, i + 1);
+        }
+        let capacity = uf.parents.capacity();
+
+        uf.clear();
+        assert_eq!(uf.count(), 0);
+        assert!(uf.parents.is_empty() && uf.sizes.is_empty());
+        assert_eq!(uf.parents.capacity(), capacity);
+
+        uf.insert(1);
+        assert_eq!(uf.count(), 1);
+    }
+}